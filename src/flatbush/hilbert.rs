@@ -32,10 +32,18 @@ where
             Envelope::Bounds { min, max } => {
                 let hilbert_max = C::from((1 << 16) - 1).unwrap();
                 let delta = max - min;
+                // A degenerate dimension has a single hilbert coordinate.
+                let scale = |d: C| {
+                    if d > C::zero() {
+                        hilbert_max / d
+                    } else {
+                        C::zero()
+                    }
+                };
                 Hilbert {
                     env,
-                    x_scale: hilbert_max / delta.x,
-                    y_scale: hilbert_max / delta.y,
+                    x_scale: scale(delta.x),
+                    y_scale: scale(delta.y),
                     x_min: min.x,
                     y_min: min.y,
                 }
@@ -177,6 +185,13 @@ mod tests {
         assert_eq!(result, 0);
     }
 
+    #[test]
+    fn hilbert_with_zero_width_rect() {
+        let total_rect = Envelope::new((2., -1.).into(), (2., 5.).into());
+        let h = Hilbert::new(total_rect);
+        assert!(h.hilbert(Position::new(2., -1.)) < h.hilbert(Position::new(2., 5.)));
+    }
+
     #[test]
     fn hilbert_ordering() {
         let total_rect = Envelope::new((0., 0.).into(), (4., 4.).into());
@@ -0,0 +1,267 @@
+use crate::planar::primitives::{Position, Segment, Triangle};
+use crate::planar::types::{
+    Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+use crate::Coordinate;
+
+/**
+ * Accumulate the centroid of a collection of components.
+ *
+ * Components of each dimension are tracked separately: areas are weighted by
+ * area, lines by length, and points counted equally.  The centroid is taken
+ * from the highest dimension with non-zero weight, so lower dimension
+ * components don't affect the centroid of higher dimension ones.  Degenerate
+ * components (eg, polygons of zero area) fall back to their boundaries.
+ */
+#[derive(Debug)]
+pub struct CentroidAccumulator<C: Coordinate> {
+    area: C,
+    area_moment: Position<C>,
+    length: C,
+    length_moment: Position<C>,
+    num_points: C,
+    point_sum: Position<C>,
+}
+
+impl<C: Coordinate> Default for CentroidAccumulator<C> {
+    fn default() -> Self {
+        CentroidAccumulator::new()
+    }
+}
+
+impl<C: Coordinate> CentroidAccumulator<C> {
+    pub fn new() -> Self {
+        let origin = Position::new(C::zero(), C::zero());
+        CentroidAccumulator {
+            area: C::zero(),
+            area_moment: origin,
+            length: C::zero(),
+            length_moment: origin,
+            num_points: C::zero(),
+            point_sum: origin,
+        }
+    }
+
+    /// The centroid of everything added so far, or None if nothing was.
+    pub fn centroid(&self) -> Option<Point<C>> {
+        if self.area != C::zero() {
+            Some(Point(self.area_moment / self.area))
+        } else if self.length != C::zero() {
+            Some(Point(self.length_moment / self.length))
+        } else if self.num_points != C::zero() {
+            Some(Point(self.point_sum / self.num_points))
+        } else {
+            None
+        }
+    }
+
+    pub fn add_position(&mut self, position: Position<C>) {
+        self.num_points = self.num_points + C::one();
+        self.point_sum = self.point_sum + position;
+    }
+
+    /// Add the positions of a line, weighting each segment by its length.
+    pub fn add_line(&mut self, positions: &[Position<C>]) {
+        let two = C::one() + C::one();
+        for pair in positions.windows(2) {
+            let length = Segment::new(pair[0], pair[1]).length();
+            self.length = self.length + length;
+            self.length_moment = self.length_moment + (pair[0] + pair[1]) * (length / two);
+        }
+        // Fallback, in case all the segments are degenerate.
+        for &position in positions {
+            self.add_position(position);
+        }
+    }
+
+    /**
+     * Add a ring, weighted by its area.
+     *
+     * The orientation of the ring doesn't matter: shells add area and holes
+     * subtract it.  The ring is also added as a line, in case the total
+     * area is zero.
+     */
+    pub fn add_ring(&mut self, positions: &[Position<C>], is_hole: bool) {
+        if let Some(&base) = positions.first() {
+            let three = C::one() + C::one() + C::one();
+            let mut ring_area = C::zero();
+            let mut ring_moment = Position::new(C::zero(), C::zero());
+            for pair in positions.windows(2) {
+                let area = Triangle::new(base, pair[0], pair[1]).signed_area();
+                ring_area = ring_area + area;
+                ring_moment = ring_moment + (base + pair[0] + pair[1]) * (area / three);
+            }
+            // Normalize so that shells count positively, and holes negatively.
+            if (ring_area < C::zero()) != is_hole {
+                ring_area = -ring_area;
+                ring_moment = ring_moment * -C::one();
+            }
+            self.area = self.area + ring_area;
+            self.area_moment = self.area_moment + ring_moment;
+        }
+        self.add_line(positions);
+    }
+
+    pub fn add_point(&mut self, point: &Point<C>) {
+        self.add_position(point.0);
+    }
+
+    pub fn add_multipoint(&mut self, multipoint: &MultiPoint<C>) {
+        multipoint.points.iter().for_each(|p| self.add_point(p));
+    }
+
+    pub fn add_linestring(&mut self, linestring: &LineString<C>) {
+        self.add_line(&linestring.positions);
+    }
+
+    pub fn add_multilinestring(&mut self, multilinestring: &MultiLineString<C>) {
+        multilinestring
+            .line_strings
+            .iter()
+            .for_each(|ls| self.add_linestring(ls));
+    }
+
+    pub fn add_polygon(&mut self, polygon: &Polygon<C>) {
+        self.add_ring(&polygon.exterior.positions, false);
+        for interior in &polygon.interiors {
+            self.add_ring(&interior.positions, true);
+        }
+    }
+
+    pub fn add_multipolygon(&mut self, multipolygon: &MultiPolygon<C>) {
+        multipolygon
+            .polygons
+            .iter()
+            .for_each(|p| self.add_polygon(p));
+    }
+
+    pub fn add_geometry(&mut self, geometry: &Geometry<C>) {
+        match geometry {
+            Geometry::Empty(_) => (),
+            Geometry::Point(x) => self.add_point(x),
+            Geometry::MultiPoint(x) => self.add_multipoint(x),
+            Geometry::LineString(x) => self.add_linestring(x),
+            Geometry::MultiLineString(x) => self.add_multilinestring(x),
+            Geometry::Polygon(x) => self.add_polygon(x),
+            Geometry::MultiPolygon(x) => self.add_multipolygon(x),
+        }
+    }
+}
+
+/**
+ * Find the centroid of a mixed collection of geometries.
+ *
+ * Only the components of the highest dimension (with non-zero weight)
+ * contribute.  If all the geometries are empty, return None.
+ */
+pub fn find_centroid<'a, C: Coordinate + 'a>(
+    geometries: impl IntoIterator<Item = &'a Geometry<C>>,
+) -> Option<Point<C>> {
+    let mut accumulator = CentroidAccumulator::new();
+    geometries
+        .into_iter()
+        .for_each(|g| accumulator.add_geometry(g));
+    accumulator.centroid()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn unit_square() -> Vec<(f64, f64)> {
+        vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]
+    }
+
+    #[test]
+    fn check_empty() {
+        let geometries: Vec<Geometry<f64>> = vec![Geometry::empty()];
+        assert_eq!(find_centroid(&geometries), None);
+    }
+
+    #[test]
+    fn check_polygon_square() {
+        let polygon = Polygon::from(unit_square());
+        assert_eq!(polygon.centroid(), Some(Point::from((0.5, 0.5))));
+    }
+
+    #[test]
+    fn check_polygon_orientation_independent() {
+        let mut ring = unit_square();
+        ring.reverse();
+        assert_eq!(
+            Polygon::from(ring).centroid(),
+            Some(Point::from((0.5, 0.5)))
+        );
+    }
+
+    #[test]
+    fn check_polygon_with_hole() {
+        // A 4x4 square with a 2x2 hole in the right half: the remaining
+        // area is 12, so the centroid is pulled left.
+        let polygon = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (2., 1.),
+                (2., 3.),
+                (4., 3.),
+                (4., 1.),
+                (2., 1.),
+            ])],
+        );
+        let centroid: Point<f64> = polygon.centroid().unwrap();
+        assert!((centroid.x() - (16. * 2. - 4. * 3.) / 12.).abs() < 1e-12);
+        assert!((centroid.y() - 2.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_degenerate_polygon_uses_boundary() {
+        let polygon = Polygon::from(vec![(0., 0.), (2., 0.), (4., 0.), (0., 0.)]);
+        assert_eq!(polygon.centroid(), Some(Point::from((2., 0.))));
+    }
+
+    #[test]
+    fn check_multipolygon_area_weighted() {
+        let mp = MultiPolygon::from(vec![
+            vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)],
+            vec![(2., 0.), (5., 0.), (5., 1.), (2., 1.), (2., 0.)],
+        ]);
+        // Areas 1 and 3, centroids at x = 0.5 and 3.5.
+        assert_eq!(mp.centroid(), Some(Point::from((2.75, 0.5))));
+    }
+
+    #[test]
+    fn check_linestring_length_weighted() {
+        let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 1.)]);
+        // Segments of length 2 and 1, midpoints (1, 0) and (2, 0.5).
+        let centroid: Point<f64> = ls.centroid().unwrap();
+        assert!((centroid.x() - 4. / 3.).abs() < 1e-12);
+        assert!((centroid.y() - 0.5 / 3.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_multilinestring() {
+        let mls = MultiLineString::from(vec![vec![(0., 0.), (1., 0.)], vec![(0., 2.), (1., 2.)]]);
+        assert_eq!(mls.centroid(), Some(Point::from((0.5, 1.))));
+    }
+
+    #[test]
+    fn check_multipoint_mean() {
+        let mp = MultiPoint::from(vec![(0., 0.), (2., 0.), (1., 3.)]);
+        assert_eq!(mp.centroid(), Some(Point::from((1., 1.))));
+    }
+
+    #[test]
+    fn check_mixed_uses_highest_dimension() {
+        let geometries = vec![
+            Geometry::from(Point::from((100., 100.))),
+            Geometry::from(LineString::from(vec![(50., 50.), (60., 50.)])),
+            Geometry::from(Polygon::from(unit_square())),
+        ];
+        assert_eq!(find_centroid(&geometries), Some(Point::from((0.5, 0.5))));
+        assert_eq!(
+            find_centroid(&geometries[..2]),
+            Some(Point::from((55., 50.)))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use super::convex_hull::find_convex_hull;
+use super::delaunay::{next_halfedge, prev_halfedge, DelaunayTriangulation};
+use crate::planar::primitives::{Position, Segment};
+use crate::planar::types::{Geometry, LineString, Polygon};
+use crate::Coordinate;
+use ordered_float::OrderedFloat;
+use std::collections::{BinaryHeap, HashMap};
+
+/**
+ * Find a concave hull of a collection of positions.
+ *
+ * This is the "chi-shape" algorithm: start with the Delaunay triangulation,
+ * whose outline is the convex hull, and repeatedly erode the triangle behind
+ * the longest boundary edge.  Erosion stops when the longest boundary edge is
+ * no longer than the threshold.  A triangle is only removed if its third
+ * position is not yet on the boundary, so the outline stays a simple ring
+ * and every position stays inside or on it.
+ *
+ * The threshold is `ratio` of the way from the shortest to the longest edge
+ * of the triangulation: 1 gives the convex hull, and 0 the most concave hull.
+ *
+ * For non-degenerate positions the result is a Polygon with a counter-
+ * clockwise exterior.  As with `find_convex_hull`, fewer than three distinct,
+ * non-collinear positions give Empty, a Point or a LineString.
+ */
+pub fn find_concave_hull<C: Coordinate>(
+    positions: impl IntoIterator<Item = Position<C>>,
+    ratio: C,
+) -> Geometry<C> {
+    let positions: Vec<Position<C>> = positions.into_iter().collect();
+    let triangulation = DelaunayTriangulation::new(&positions);
+    if triangulation.num_triangles() == 0 {
+        return find_convex_hull(triangulation.positions);
+    }
+    let positions = &triangulation.positions;
+    let triangles = &triangulation.triangles;
+    let halfedges = &triangulation.halfedges;
+    let edge_length = |e: usize| {
+        Segment::new(
+            positions[triangles[e]],
+            positions[triangles[next_halfedge(e)]],
+        )
+        .length()
+    };
+
+    let (min_length, max_length) = (0..triangles.len())
+        .map(edge_length)
+        .fold((C::infinity(), C::neg_infinity()), |(min, max), l| {
+            (min.min(l), max.max(l))
+        });
+    let threshold = min_length + ratio * (max_length - min_length);
+
+    let mut is_removed = vec![false; triangulation.num_triangles()];
+    let mut is_on_boundary = vec![false; positions.len()];
+    let mut heap = BinaryHeap::new();
+    for e in (0..triangles.len()).filter(|&e| halfedges[e].is_none()) {
+        is_on_boundary[triangles[e]] = true;
+        heap.push((OrderedFloat(edge_length(e)), e));
+    }
+
+    while let Some((OrderedFloat(length), e)) = heap.pop() {
+        if length <= threshold {
+            break;
+        }
+        let far = triangles[prev_halfedge(e)];
+        if is_removed[e / 3] || is_on_boundary[far] {
+            continue;
+        }
+        is_removed[e / 3] = true;
+        is_on_boundary[far] = true;
+        // The other two edges are interior (since `far` wasn't on the
+        // boundary), so their neighbors are now on the boundary.
+        for &side in &[next_halfedge(e), prev_halfedge(e)] {
+            if let Some(opposite) = halfedges[side] {
+                heap.push((OrderedFloat(edge_length(opposite)), opposite));
+            }
+        }
+    }
+
+    // Each boundary position has exactly one outgoing boundary edge.
+    let next_position: HashMap<usize, usize> = (0..triangles.len())
+        .filter(|&e| !is_removed[e / 3])
+        .filter(|&e| match halfedges[e] {
+            Some(opposite) => is_removed[opposite / 3],
+            None => true,
+        })
+        .map(|e| (triangles[e], triangles[next_halfedge(e)]))
+        .collect();
+    // Positions on the convex hull are never eroded off the boundary.
+    let start = triangulation.hull[0];
+    match _trace_boundary(&next_position, start, positions.len()) {
+        Some(ids) => {
+            let ring = ids.iter().chain(std::iter::once(&start));
+            Geometry::from(Polygon::from(LineString::collect_from(
+                ring.map(|&id| positions[id]),
+            )))
+        }
+        // The erosion should always leave a simple ring; if not, fall back
+        // to the convex hull rather than returning an invalid polygon.
+        None => find_convex_hull(positions.iter().copied()),
+    }
+}
+
+/**
+ * Follow the boundary from `start` until it returns there, returning the
+ * positions visited.  Returns None if the boundary breaks off, or doesn't
+ * return to `start` within `max_len` positions.
+ */
+fn _trace_boundary(
+    next_position: &HashMap<usize, usize>,
+    start: usize,
+    max_len: usize,
+) -> Option<Vec<usize>> {
+    let mut ids = vec![start];
+    let mut current = *next_position.get(&start)?;
+    while current != start {
+        if ids.len() >= max_len {
+            return None;
+        }
+        ids.push(current);
+        current = *next_position.get(&current)?;
+    }
+    Some(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planar::properties::Area;
+    use crate::planar::types::MultiPoint;
+
+    /// A C shape of grid positions, with a gap much wider than the spacing.
+    fn c_shape() -> MultiPoint<f64> {
+        let mut coords = Vec::new();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                if i <= 1 || j <= 1 || j >= 9 {
+                    coords.push((i as f64, j as f64));
+                }
+            }
+        }
+        MultiPoint::from(coords)
+    }
+
+    #[test]
+    fn check_ratio_one_is_convex_hull() {
+        let mp = c_shape();
+        let hull = mp.concave_hull(1.).as_polygon().unwrap();
+        let convex = mp.convex_hull().as_polygon().unwrap();
+        assert_eq!(hull.area(), 100.);
+        assert_eq!(convex.area(), 100.);
+    }
+
+    #[test]
+    fn check_c_shape_is_concave() {
+        let mp = c_shape();
+        let hull = mp.concave_hull(0.).as_polygon().unwrap();
+        assert!(hull.validate().is_ok());
+        // The gap is eroded, leaving the arms.
+        assert_eq!(hull.area(), 100. - 9. * 8.);
+    }
+
+    #[test]
+    fn check_degenerate() {
+        let mp = MultiPoint::from(vec![(0., 0.), (1., 1.), (2., 2.)]);
+        assert_eq!(
+            mp.concave_hull(0.),
+            Geometry::from(LineString::from(vec![(0., 0.), (2., 2.)]))
+        );
+        let empty: MultiPoint<f64> = MultiPoint::new(Vec::new());
+        assert_eq!(empty.concave_hull(0.5), Geometry::empty());
+    }
+
+    #[test]
+    fn check_trace_boundary() {
+        let cycle: HashMap<usize, usize> = vec![(0, 2), (2, 1), (1, 0)].into_iter().collect();
+        assert_eq!(_trace_boundary(&cycle, 0, 3), Some(vec![0, 2, 1]));
+        let broken: HashMap<usize, usize> = vec![(0, 2), (2, 1)].into_iter().collect();
+        assert_eq!(_trace_boundary(&broken, 0, 3), None);
+        // A loop that never returns to the start.
+        let lasso: HashMap<usize, usize> = vec![(0, 1), (1, 2), (2, 1)].into_iter().collect();
+        assert_eq!(_trace_boundary(&lasso, 0, 3), None);
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 use crate::planar::primitives::{Position, PositionLocation, Segment};
-use crate::planar::types::LineString;
+use crate::planar::types::{Geometry, LineString, Point, Polygon};
 use crate::Coordinate;
 /**
  * Algorithms for calculating convex hulls.
@@ -63,6 +63,67 @@ pub(crate) fn find_convex_hull_of_simple_loop<C: Coordinate>(
     LineString::collect_from(deque.into_iter().rev())
 }
 
+/**
+ * This finds the convex hull of any collection of positions.
+ *
+ * This is Andrew's monotone chain algorithm: the positions are sorted, and
+ * the lower and upper halves of the hull are built by scanning them in each
+ * direction, dropping positions that don't make a left turn.
+ *
+ * The result depends on how degenerate the hull is: Empty for no positions,
+ * a Point if they are all the same, a two-position LineString if they are all
+ * collinear, and otherwise a Polygon with a counter-clockwise exterior.
+ * Positions with NaN coordinates are ignored.
+ */
+pub fn find_convex_hull<C: Coordinate>(
+    positions: impl IntoIterator<Item = Position<C>>,
+) -> Geometry<C> {
+    let mut positions: Vec<Position<C>> = positions
+        .into_iter()
+        .filter(|p| !p.x.is_nan() && !p.y.is_nan())
+        .collect();
+    positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    positions.dedup();
+
+    match positions.len() {
+        0 => return Geometry::empty(),
+        1 => return Geometry::from(Point(positions[0])),
+        _ => (),
+    }
+
+    let mut hull: Vec<Position<C>> = Vec::new();
+    // The lower half, left to right.
+    for &pos in &positions {
+        _push_hull_position(&mut hull, pos, 2);
+    }
+    // The upper half, right to left, without popping into the lower half.
+    let lower_len = hull.len();
+    for &pos in positions.iter().rev().skip(1) {
+        _push_hull_position(&mut hull, pos, lower_len + 1);
+    }
+
+    // If the positions are collinear, the halves are just first, last, first.
+    if hull.len() <= 3 {
+        return Geometry::from(LineString::new(hull[..2].to_vec()));
+    }
+    Geometry::from(Polygon::from(LineString::new(hull)))
+}
+
+/// Push the position, first popping any that it would make a non-left turn.
+fn _push_hull_position<C: Coordinate>(
+    hull: &mut Vec<Position<C>>,
+    pos: Position<C>,
+    min_len: usize,
+) {
+    while hull.len() >= min_len
+        && _triple_location(hull[hull.len() - 2], hull[hull.len() - 1], pos)
+            != PositionLocation::Left
+    {
+        hull.pop();
+    }
+    hull.push(pos);
+}
+
 fn _triple_location<C: Coordinate>(
     a: Position<C>,
     b: Position<C>,
@@ -74,6 +135,7 @@ fn _triple_location<C: Coordinate>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::planar::types::{MultiLineString, MultiPoint};
 
     fn assert_loops_equiv<C: Coordinate>(loop1: &mut LineString<C>, loop2: &mut LineString<C>) {
         assert!(loop1.is_closed());
@@ -143,4 +205,74 @@ mod tests {
         ]);
         assert_loops_equiv(&mut hull, &mut target);
     }
+
+    #[test]
+    fn check_convex_hull_empty() {
+        let hull: Geometry<f64> = find_convex_hull(Vec::new());
+        assert_eq!(hull, Geometry::empty());
+    }
+
+    #[test]
+    fn check_convex_hull_single_position() {
+        let positions = vec![Position::new(1., 2.), Position::new(1., 2.)];
+        assert_eq!(
+            find_convex_hull(positions),
+            Geometry::from(Point::from((1., 2.)))
+        );
+    }
+
+    #[test]
+    fn check_convex_hull_collinear() {
+        let positions = vec![(1., 1.), (0., 0.), (3., 3.), (2., 2.)]
+            .into_iter()
+            .map(Position::from);
+        assert_eq!(
+            find_convex_hull(positions),
+            Geometry::from(LineString::from(vec![(0., 0.), (3., 3.)]))
+        );
+    }
+
+    #[test]
+    fn check_convex_hull_positions() {
+        let positions = vec![(0., 0.), (2., 0.), (1., 1.), (1., 0.), (2., 2.), (0., 2.)]
+            .into_iter()
+            .map(Position::from);
+        assert_eq!(
+            find_convex_hull(positions),
+            Geometry::from(Polygon::from(vec![
+                (0., 0.),
+                (2., 0.),
+                (2., 2.),
+                (0., 2.),
+                (0., 0.)
+            ]))
+        );
+    }
+
+    #[test]
+    fn check_convex_hull_of_geometries() {
+        let mls = MultiLineString::from(vec![vec![(0., 0.), (1., 0.)], vec![(0., 1.), (1., 1.)]]);
+        let square = Polygon::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+        assert_eq!(mls.convex_hull(), Geometry::from(square));
+        let ls = LineString::from(vec![(0., 0.), (1., 0.)]);
+        assert_eq!(ls.convex_hull(), Geometry::from(ls.clone()));
+        let mp: MultiPoint<f64> = MultiPoint::new(Vec::new());
+        assert_eq!(Geometry::from(mp).convex_hull(), Geometry::empty());
+    }
+
+    #[test]
+    fn check_convex_hull_of_degenerate_polygons() {
+        let empty: Polygon<f64> = Polygon::from(LineString::new(Vec::new()));
+        assert_eq!(empty.convex_hull(), Geometry::empty());
+        let collapsed = Polygon::from(vec![(0., 0.), (1., 0.), (0., 0.)]);
+        assert_eq!(
+            collapsed.convex_hull(),
+            Geometry::from(LineString::from(vec![(0., 0.), (1., 0.)]))
+        );
+        let star = Polygon::from(vec![(0., 0.), (1., 0.4), (2., 0.), (1., 2.), (0., 0.)]);
+        assert_eq!(
+            star.convex_hull(),
+            Geometry::from(Polygon::from(vec![(0., 0.), (2., 0.), (1., 2.), (0., 0.)]))
+        );
+    }
 }
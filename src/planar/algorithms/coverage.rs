@@ -0,0 +1,298 @@
+use super::simplify::{simplify_rdp, MIN_RING_POSITIONS};
+use crate::planar::primitives::{Position, SafePosition};
+use crate::planar::types::{LineString, Polygon};
+use crate::Coordinate;
+use std::collections::{HashMap, HashSet};
+
+/// A chain of ring positions, keyed by its canonical orientation.
+type ChainKey<C> = Vec<SafePosition<C>>;
+/// The chains of a ring, and whether each is traversed in reverse.
+type RingChains<C> = Vec<(ChainKey<C>, bool)>;
+
+/**
+ * Simplify a coverage of polygons, keeping shared borders consistent.
+ *
+ * The rings of the polygons are split into chains at their nodes: positions
+ * where other than two distinct edges meet.  Each chain is either used by a
+ * single ring, or shared between neighboring rings.  Every distinct chain is
+ * simplified once with Ramer–Douglas–Peucker, and the rings are rebuilt from
+ * the simplified chains, so neighbors still match exactly.  Nodes are never
+ * removed, and rings keep at least 4 positions.
+ *
+ * Shared borders are found by exact equality of positions, so the polygons
+ * should be noded consistently (neighbors have the same vertices along the
+ * border).  Rings that are not closed are returned unchanged.  Like plain
+ * simplification, this does not prevent chains from crossing each other.
+ */
+pub fn simplify_coverage<C: Coordinate>(polygons: &[Polygon<C>], tolerance: C) -> Vec<Polygon<C>> {
+    let rings: Vec<&LineString<C>> = polygons
+        .iter()
+        .flat_map(|p| std::iter::once(&p.exterior).chain(p.interiors.iter()))
+        .collect();
+
+    let nodes = _find_nodes(&rings);
+
+    let ring_chains: Vec<Option<RingChains<C>>> =
+        rings.iter().map(|ring| _split_ring(ring, &nodes)).collect();
+
+    // Simplify each distinct chain once.  Closed chains keep 4 positions,
+    // other chains keep their endpoints.
+    let mut min_positions: HashMap<ChainKey<C>, usize> = HashMap::new();
+    for (key, _) in ring_chains.iter().flatten().flatten() {
+        let min = if key.first() == key.last() {
+            MIN_RING_POSITIONS
+        } else {
+            2
+        };
+        min_positions.insert(key.clone(), min);
+    }
+    let mut simplified: HashMap<ChainKey<C>, Vec<Position<C>>> = HashMap::new();
+    for (key, &min) in &min_positions {
+        simplified.insert(key.clone(), _simplify_chain(key, tolerance, min));
+    }
+
+    // A ring made of 2 or 3 chains might have collapsed.  If so, keep another
+    // position in its longest chain that can still grow, and try again.
+    loop {
+        let mut grown = Vec::new();
+        for chains in ring_chains.iter().flatten() {
+            let num_positions: usize = chains.iter().map(|(k, _)| simplified[k].len() - 1).sum();
+            if num_positions + 1 >= MIN_RING_POSITIONS {
+                continue;
+            }
+            if let Some((key, _)) = chains
+                .iter()
+                .filter(|(k, _)| simplified[k].len() < k.len())
+                .max_by_key(|(k, _)| k.len())
+            {
+                grown.push(key.clone());
+            }
+        }
+        if grown.is_empty() {
+            break;
+        }
+        for key in grown {
+            let min = min_positions[&key].max(simplified[&key].len()) + 1;
+            min_positions.insert(key.clone(), min);
+            simplified.insert(key.clone(), _simplify_chain(&key, tolerance, min));
+        }
+    }
+
+    let mut new_rings = rings
+        .iter()
+        .zip(ring_chains)
+        .map(|(ring, chains)| match chains {
+            None => (*ring).clone(),
+            Some(chains) => _join_chains(&chains, &simplified),
+        });
+    polygons
+        .iter()
+        .map(|p| {
+            let exterior = new_rings.next().unwrap();
+            let interiors = new_rings.by_ref().take(p.interiors.len()).collect();
+            Polygon::new(exterior, interiors)
+        })
+        .collect()
+}
+
+fn _simplify_chain<C: Coordinate>(
+    key: &[SafePosition<C>],
+    tolerance: C,
+    min_positions: usize,
+) -> Vec<Position<C>> {
+    let positions: Vec<Position<C>> = key.iter().map(|&p| Position::from(p)).collect();
+    simplify_rdp(&positions, tolerance, min_positions)
+}
+
+/// Find the positions where other than two distinct edges meet.
+fn _find_nodes<C: Coordinate>(rings: &[&LineString<C>]) -> HashSet<SafePosition<C>> {
+    let mut neighbors: HashMap<SafePosition<C>, HashSet<SafePosition<C>>> = HashMap::new();
+    for ring in rings.iter().filter(|r| r.is_closed()) {
+        for segment in ring.segments_iter() {
+            if let (Ok(start), Ok(end)) = (segment.start.to_hashable(), segment.end.to_hashable()) {
+                neighbors.entry(start).or_default().insert(end);
+                neighbors.entry(end).or_default().insert(start);
+            }
+        }
+    }
+    neighbors
+        .into_iter()
+        .filter(|(_, n)| n.len() != 2)
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/**
+ * Split a ring into chains between nodes.
+ *
+ * Each chain is returned as its canonical key and whether the ring traverses
+ * it in reverse.  A ring without nodes is a single closed chain, starting at
+ * its least position.  Returns None if the ring is not closed, or has NaNs.
+ */
+fn _split_ring<C: Coordinate>(
+    ring: &LineString<C>,
+    nodes: &HashSet<SafePosition<C>>,
+) -> Option<RingChains<C>> {
+    if !ring.is_closed() {
+        return None;
+    }
+    let mut positions = ring.positions[..ring.num_points() - 1]
+        .iter()
+        .map(|p| p.to_hashable().ok())
+        .collect::<Option<Vec<SafePosition<C>>>>()?;
+
+    let start = match positions.iter().position(|p| nodes.contains(p)) {
+        Some(index) => index,
+        None => {
+            let (index, _) = positions.iter().enumerate().min_by_key(|(_, p)| *p)?;
+            index
+        }
+    };
+    positions.rotate_left(start);
+    positions.push(positions[0]);
+
+    let mut chains = Vec::new();
+    let mut chain = vec![positions[0]];
+    for &position in &positions[1..] {
+        chain.push(position);
+        if nodes.contains(&position) || chain.len() == positions.len() {
+            chains.push(_canonical_chain(std::mem::replace(
+                &mut chain,
+                vec![position],
+            )));
+        }
+    }
+    Some(chains)
+}
+
+/// Orient the chain so that its positions are lexicographically least.
+fn _canonical_chain<C: Coordinate>(chain: Vec<SafePosition<C>>) -> (ChainKey<C>, bool) {
+    let reversed: Vec<SafePosition<C>> = chain.iter().rev().copied().collect();
+    if reversed < chain {
+        (reversed, true)
+    } else {
+        (chain, false)
+    }
+}
+
+fn _join_chains<C: Coordinate>(
+    chains: &[(ChainKey<C>, bool)],
+    simplified: &HashMap<ChainKey<C>, Vec<Position<C>>>,
+) -> LineString<C> {
+    let mut positions: Vec<Position<C>> = Vec::new();
+    for (key, is_reversed) in chains {
+        let mut chain = simplified[key].clone();
+        if *is_reversed {
+            chain.reverse();
+        }
+        if !positions.is_empty() {
+            positions.pop();
+        }
+        positions.extend(chain);
+    }
+    LineString::new(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_shared_edge_simplified_once() {
+        // Two squares sharing a wiggly border along x = 1.
+        let left = Polygon::from(vec![
+            (0., 0.),
+            (1., 0.),
+            (1.05, 0.3),
+            (0.95, 0.6),
+            (1., 1.),
+            (0., 1.),
+            (0., 0.),
+        ]);
+        let right = Polygon::from(vec![
+            (1., 0.),
+            (2., 0.),
+            (2., 1.),
+            (1., 1.),
+            (0.95, 0.6),
+            (1.05, 0.3),
+            (1., 0.),
+        ]);
+        let result = simplify_coverage(&[left, right], 0.1);
+        assert_eq!(
+            result[0].exterior,
+            LineString::from(vec![(1., 0.), (1., 1.), (0., 1.), (0., 0.), (1., 0.)])
+        );
+        assert_eq!(
+            result[1].exterior,
+            LineString::from(vec![(1., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 0.)])
+        );
+        assert!(result.iter().all(|p| p.validate().is_ok()));
+    }
+
+    #[test]
+    fn check_shared_edge_matches_exactly() {
+        // The shared border is within tolerance of a few different shortcuts;
+        // both neighbors must pick the same one.
+        let left = Polygon::from(vec![
+            (1., 0.),
+            (1.2, 1.),
+            (0.8, 2.),
+            (1.2, 3.),
+            (1., 4.),
+            (0., 4.),
+            (0., 0.),
+            (1., 0.),
+        ]);
+        let right = Polygon::from(vec![
+            (2., 0.),
+            (2., 4.),
+            (1., 4.),
+            (1.2, 3.),
+            (0.8, 2.),
+            (1.2, 1.),
+            (1., 0.),
+            (2., 0.),
+        ]);
+        let result = simplify_coverage(&[left, right], 0.3);
+        let border = |ls: &LineString<f64>| {
+            let mut positions: Vec<Position<f64>> = ls
+                .positions
+                .iter()
+                .filter(|p| p.x > 0.5 && p.x < 1.5)
+                .copied()
+                .collect();
+            positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            positions.dedup();
+            positions
+        };
+        assert_eq!(border(&result[0].exterior), border(&result[1].exterior));
+        assert!(border(&result[0].exterior).len() < 5);
+    }
+
+    #[test]
+    fn check_island_in_hole() {
+        let island_ring = vec![(1., 1.), (2., 1.1), (3., 1.), (3., 3.), (1., 3.), (1., 1.)];
+        let shell = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(island_ring.clone())],
+        );
+        let island = Polygon::from(island_ring.into_iter().rev().collect::<Vec<_>>());
+        let result = simplify_coverage(&[shell, island], 0.5);
+        assert_eq!(result[0].interiors[0].num_points(), 5);
+        let mut hole: Vec<Position<f64>> = result[0].interiors[0].positions.clone();
+        let mut island: Vec<Position<f64>> = result[1].exterior.positions.clone();
+        hole.pop();
+        island.pop();
+        hole.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        island.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(hole, island);
+    }
+
+    #[test]
+    fn check_unclosed_ring_unchanged() {
+        let polygon = Polygon::from(vec![(0., 0.), (1., 0.), (1., 1.), (0.5, 1.01), (0., 1.)]);
+        let result = simplify_coverage(std::slice::from_ref(&polygon), 0.1);
+        assert_eq!(result, vec![polygon]);
+    }
+}
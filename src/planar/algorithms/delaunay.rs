@@ -0,0 +1,608 @@
+use crate::planar::primitives::{incircle, orient2d, Position, Triangle};
+use crate::Coordinate;
+
+/**
+ * A Delaunay triangulation of a set of positions.
+ *
+ * Triangles are stored as consecutive triples of indices into `positions`,
+ * in counter-clockwise order.  Halfedge `e` goes from `triangles[e]` to
+ * `triangles[next_halfedge(e)]`, and `halfedges[e]` is the opposite halfedge
+ * in the neighboring triangle, or None on the convex hull.  `hull` is the
+ * convex hull, as counter-clockwise indices into `positions`.
+ *
+ * Duplicate positions (and positions with NaN coordinates) are left out of
+ * the triangulation.  If the positions are all collinear there are no
+ * triangles, and the hull is the positions sorted along their line.
+ */
+#[derive(Debug, Clone)]
+pub struct DelaunayTriangulation<C: Coordinate> {
+    pub positions: Vec<Position<C>>,
+    pub triangles: Vec<usize>,
+    pub halfedges: Vec<Option<usize>>,
+    pub hull: Vec<usize>,
+}
+
+/// The next halfedge in the same triangle.
+pub fn next_halfedge(e: usize) -> usize {
+    if e % 3 == 2 {
+        e - 2
+    } else {
+        e + 1
+    }
+}
+
+/// The previous halfedge in the same triangle.
+pub fn prev_halfedge(e: usize) -> usize {
+    match e % 3 {
+        0 => e + 2,
+        _ => e - 1,
+    }
+}
+
+impl<C: Coordinate> DelaunayTriangulation<C> {
+    /**
+     * Triangulate the positions.
+     *
+     * This is the sweep-hull algorithm of the Delaunator library: starting
+     * from a seed triangle near the center, the positions are added in order
+     * of distance from it.  Each new position is outside the current convex
+     * hull, so it is joined to the hull edges it can see, and the new edges
+     * are flipped until they satisfy the Delaunay condition.
+     */
+    pub fn new(positions: &[Position<C>]) -> Self {
+        let mut builder = Builder::new(positions);
+        builder.triangulate();
+        let hull = builder.collect_hull();
+        DelaunayTriangulation {
+            positions: positions.to_vec(),
+            triangles: builder.triangles,
+            halfedges: builder.halfedges,
+            hull,
+        }
+    }
+
+    pub fn num_triangles(&self) -> usize {
+        self.triangles.len() / 3
+    }
+
+    /// The triangles, as counter-clockwise Triangles.
+    pub fn to_triangles(&self) -> Vec<Triangle<C>> {
+        self.triangles
+            .chunks_exact(3)
+            .map(|t| {
+                Triangle::new(
+                    self.positions[t[0]],
+                    self.positions[t[1]],
+                    self.positions[t[2]],
+                )
+            })
+            .collect()
+    }
+
+    /**
+     * The neighbors of each triangle, by index.  Neighbor `k` of a triangle
+     * shares the edge from its position `k` to position `(k + 1) % 3`, and is
+     * None if that edge is on the convex hull.
+     */
+    pub fn triangle_neighbors(&self) -> Vec<[Option<usize>; 3]> {
+        self.halfedges
+            .chunks_exact(3)
+            .map(|h| {
+                [
+                    h[0].map(|e| e / 3),
+                    h[1].map(|e| e / 3),
+                    h[2].map(|e| e / 3),
+                ]
+            })
+            .collect()
+    }
+}
+
+struct Builder<'a, C: Coordinate> {
+    positions: &'a [Position<C>],
+    triangles: Vec<usize>,
+    halfedges: Vec<Option<usize>>,
+    // The convex hull, as a circular doubly-linked list.  Positions not on
+    // the hull have `hull_next[i] == i`.
+    hull_start: usize,
+    hull_prev: Vec<usize>,
+    hull_next: Vec<usize>,
+    // The halfedge from each hull position to the next.
+    hull_tri: Vec<usize>,
+    // Hull positions, hashed by their angle around the center.
+    hull_hash: Vec<Option<usize>>,
+    center: Position<C>,
+}
+
+impl<'a, C: Coordinate> Builder<'a, C> {
+    fn new(positions: &'a [Position<C>]) -> Self {
+        let n = positions.len();
+        let hash_size = (n as f64).sqrt().ceil().max(1.) as usize;
+        Builder {
+            positions,
+            triangles: Vec::with_capacity(3 * n),
+            halfedges: Vec::with_capacity(3 * n),
+            hull_start: 0,
+            hull_prev: (0..n).collect(),
+            hull_next: (0..n).collect(),
+            hull_tri: vec![0; n],
+            hull_hash: vec![None; hash_size],
+            center: Position::new(C::zero(), C::zero()),
+        }
+    }
+
+    fn triangulate(&mut self) {
+        let positions = self.positions;
+        let ids: Vec<usize> = (0..positions.len())
+            .filter(|&i| !positions[i].x.is_nan() && !positions[i].y.is_nan())
+            .collect();
+        let (i0, i1, i2) = match _find_seed_triangle(positions, &ids) {
+            Some(seed) => seed,
+            None => {
+                self._build_collinear_hull(ids);
+                return;
+            }
+        };
+        let (p0, p1, p2) = (positions[i0], positions[i1], positions[i2]);
+        self.center = p0 + _circumcenter_offset(p0, p1, p2);
+
+        // Sort the positions by distance from the seed circumcenter.
+        let center = self.center;
+        let mut ids = ids;
+        let distance = |i: usize| _distance_squared(center, positions[i]);
+        ids.sort_by(|&a, &b| distance(a).partial_cmp(&distance(b)).unwrap());
+
+        self.hull_start = i0;
+        self.hull_next[i0] = i1;
+        self.hull_prev[i2] = i1;
+        self.hull_next[i1] = i2;
+        self.hull_prev[i0] = i2;
+        self.hull_next[i2] = i0;
+        self.hull_prev[i1] = i0;
+        self.hull_tri[i0] = 0;
+        self.hull_tri[i1] = 1;
+        self.hull_tri[i2] = 2;
+        for &i in &[i0, i1, i2] {
+            self._hash_hull_position(i);
+        }
+        self._add_triangle(i0, i1, i2, None, None, None);
+
+        let mut previous: Option<Position<C>> = None;
+        for i in ids {
+            let p = positions[i];
+            if previous == Some(p) || i == i0 || i == i1 || i == i2 {
+                continue;
+            }
+            previous = Some(p);
+            self._add_position(i);
+        }
+    }
+
+    /// Add a position outside the current hull.
+    fn _add_position(&mut self, i: usize) {
+        let p = self.positions[i];
+
+        // Find a hull edge visible from the position, starting near its angle.
+        let key = self._hash_key(p);
+        let hash_size = self.hull_hash.len();
+        let mut start = self.hull_start;
+        for j in 0..hash_size {
+            if let Some(s) = self.hull_hash[(key + j) % hash_size] {
+                if self.hull_next[s] != s {
+                    start = s;
+                    break;
+                }
+            }
+        }
+        start = self.hull_prev[start];
+        let mut e = start;
+        while !self._is_visible(e, self.hull_next[e], p) {
+            e = self.hull_next[e];
+            if e == start {
+                // Not outside the hull (eg, a near-duplicate); skip it.
+                return;
+            }
+        }
+
+        // Join the position to the first visible edge.
+        let q = self.hull_next[e];
+        let t = self._add_triangle(e, i, q, None, None, Some(self.hull_tri[e]));
+        self.hull_tri[i] = self._legalize(t + 2);
+        self.hull_tri[e] = t;
+
+        // Walk forward, joining the other visible edges.
+        let mut n = q;
+        loop {
+            let q = self.hull_next[n];
+            if !self._is_visible(n, q, p) {
+                break;
+            }
+            let t = self._add_triangle(
+                n,
+                i,
+                q,
+                Some(self.hull_tri[i]),
+                None,
+                Some(self.hull_tri[n]),
+            );
+            self.hull_tri[i] = self._legalize(t + 2);
+            self.hull_next[n] = n;
+            n = q;
+        }
+
+        // Walk backward, if the first visible edge might not have been the first.
+        if e == start {
+            loop {
+                let q = self.hull_prev[e];
+                if !self._is_visible(q, e, p) {
+                    break;
+                }
+                let t = self._add_triangle(
+                    q,
+                    i,
+                    e,
+                    None,
+                    Some(self.hull_tri[e]),
+                    Some(self.hull_tri[q]),
+                );
+                self._legalize(t + 2);
+                self.hull_tri[q] = t;
+                self.hull_next[e] = e;
+                e = q;
+            }
+        }
+
+        self.hull_start = e;
+        self.hull_prev[i] = e;
+        self.hull_next[e] = i;
+        self.hull_prev[n] = i;
+        self.hull_next[i] = n;
+        self._hash_hull_position(i);
+        self._hash_hull_position(e);
+    }
+
+    /// The hull edge from `a` to `b` is visible if `p` is strictly to its right.
+    fn _is_visible(&self, a: usize, b: usize, p: Position<C>) -> bool {
+        orient2d(self.positions[a], self.positions[b], p) < C::zero()
+    }
+
+    fn _add_triangle(
+        &mut self,
+        i0: usize,
+        i1: usize,
+        i2: usize,
+        a: Option<usize>,
+        b: Option<usize>,
+        c: Option<usize>,
+    ) -> usize {
+        let t = self.triangles.len();
+        self.triangles.extend_from_slice(&[i0, i1, i2]);
+        self.halfedges.extend_from_slice(&[None, None, None]);
+        self._link(t, a);
+        self._link(t + 1, b);
+        self._link(t + 2, c);
+        t
+    }
+
+    fn _link(&mut self, a: usize, b: Option<usize>) {
+        self.halfedges[a] = b;
+        if let Some(b) = b {
+            self.halfedges[b] = Some(a);
+        }
+    }
+
+    /**
+     * Flip the halfedge `a` (and the edges it exposes) until they satisfy the
+     * Delaunay condition.  Returns the halfedge before `a` in its triangle,
+     * which is where the caller's next hull edge ended up.
+     */
+    fn _legalize(&mut self, a: usize) -> usize {
+        let mut stack: Vec<usize> = Vec::new();
+        let mut a = a;
+        let mut ar;
+        loop {
+            ar = prev_halfedge(a);
+            let b = match self.halfedges[a] {
+                Some(b) => b,
+                None => match stack.pop() {
+                    Some(next) => {
+                        a = next;
+                        continue;
+                    }
+                    None => break,
+                },
+            };
+
+            //        pl                  pl
+            //       /||\                /  \
+            //    al/ || \bl          al/    \a
+            //     /  ||  \            /      \
+            //    /  a||b  \   flip   /___ar___\
+            //  p0\   ||   /p1  =>  p0\---bl---/p1
+            //     \  ||  /            \      /
+            //    ar\ || /br           b\    /br
+            //       \||/                \  /
+            //        pr                  pr
+            let al = next_halfedge(a);
+            let bl = prev_halfedge(b);
+            let p0 = self.triangles[ar];
+            let pr = self.triangles[a];
+            let pl = self.triangles[al];
+            let p1 = self.triangles[bl];
+            let positions = self.positions;
+            let is_illegal =
+                incircle(positions[pr], positions[pl], positions[p0], positions[p1]) > C::zero();
+
+            if is_illegal {
+                self.triangles[a] = p1;
+                self.triangles[b] = p0;
+                let hbl = self.halfedges[bl];
+                if hbl.is_none() {
+                    // The flipped edge was on the hull; fix its reference.
+                    let mut e = self.hull_start;
+                    loop {
+                        if self.hull_tri[e] == bl {
+                            self.hull_tri[e] = a;
+                            break;
+                        }
+                        e = self.hull_prev[e];
+                        if e == self.hull_start {
+                            break;
+                        }
+                    }
+                }
+                self._link(a, hbl);
+                self._link(b, self.halfedges[ar]);
+                self._link(ar, Some(bl));
+                stack.push(next_halfedge(b));
+            } else {
+                match stack.pop() {
+                    Some(next) => a = next,
+                    None => break,
+                }
+            }
+        }
+        ar
+    }
+
+    fn _hash_key(&self, p: Position<C>) -> usize {
+        let d = p - self.center;
+        let hash_size = self.hull_hash.len();
+        let angle = _pseudo_angle(d.x, d.y) * C::from(hash_size).unwrap();
+        angle.floor().to_usize().unwrap_or(0) % hash_size
+    }
+
+    fn _hash_hull_position(&mut self, i: usize) {
+        let key = self._hash_key(self.positions[i]);
+        self.hull_hash[key] = Some(i);
+    }
+
+    /// With no triangles, the hull is the distinct positions along their line.
+    fn _build_collinear_hull(&mut self, mut ids: Vec<usize>) {
+        let positions = self.positions;
+        ids.sort_by(|&a, &b| positions[a].partial_cmp(&positions[b]).unwrap());
+        ids.dedup_by(|a, b| positions[*a] == positions[*b]);
+        if let Some(&first) = ids.first() {
+            self.hull_start = first;
+            for pair in ids.windows(2) {
+                self.hull_next[pair[0]] = pair[1];
+            }
+            let last = ids[ids.len() - 1];
+            self.hull_next[last] = last;
+        }
+    }
+
+    fn collect_hull(&self) -> Vec<usize> {
+        let mut hull = Vec::new();
+        if self.positions.is_empty()
+            || self.positions[self.hull_start].x.is_nan()
+            || self.positions[self.hull_start].y.is_nan()
+        {
+            return hull;
+        }
+        let mut e = self.hull_start;
+        loop {
+            hull.push(e);
+            e = self.hull_next[e];
+            if e == self.hull_start || e == hull[hull.len() - 1] {
+                break;
+            }
+        }
+        hull
+    }
+}
+
+/**
+ * Pick three positions near the center to start: the closest to the center
+ * of the envelope, its closest neighbor, and the position making the
+ * smallest circumcircle with them.  They are returned counter-clockwise.
+ * Returns None if all the positions are collinear.
+ */
+fn _find_seed_triangle<C: Coordinate>(
+    positions: &[Position<C>],
+    ids: &[usize],
+) -> Option<(usize, usize, usize)> {
+    let first = *ids.first()?;
+    let (min, max) = ids
+        .iter()
+        .fold((positions[first], positions[first]), |(min, max), &i| {
+            (min.min(positions[i]), max.max(positions[i]))
+        });
+    let center = (min + max) / (C::one() + C::one());
+
+    let closest_to = |target: Position<C>, skip: &[usize]| {
+        ids.iter()
+            .filter(|i| !skip.contains(i))
+            .map(|&i| (i, _distance_squared(target, positions[i])))
+            .filter(|&(_, d)| d > C::zero() || skip.is_empty())
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(i, _)| i)
+    };
+    let i0 = closest_to(center, &[])?;
+    let i1 = closest_to(positions[i0], &[i0])?;
+    let (p0, p1) = (positions[i0], positions[i1]);
+
+    let mut min_radius = C::infinity();
+    let mut seed = None;
+    for &i in ids {
+        if i == i0 || i == i1 {
+            continue;
+        }
+        let radius = _distance_squared(
+            Position::new(C::zero(), C::zero()),
+            _circumcenter_offset(p0, p1, positions[i]),
+        );
+        if radius < min_radius {
+            min_radius = radius;
+            seed = Some(i);
+        }
+    }
+    let i2 = seed?;
+    if orient2d(p0, p1, positions[i2]) < C::zero() {
+        Some((i0, i2, i1))
+    } else {
+        Some((i0, i1, i2))
+    }
+}
+
+fn _distance_squared<C: Coordinate>(a: Position<C>, b: Position<C>) -> C {
+    let d = b - a;
+    Position::dot(d, d)
+}
+
+/**
+ * The circumcenter of abc, relative to a.  If abc are collinear, the result
+ * has infinite or NaN coordinates.
+ */
+fn _circumcenter_offset<C: Coordinate>(
+    a: Position<C>,
+    b: Position<C>,
+    c: Position<C>,
+) -> Position<C> {
+    let (d, e) = (b - a, c - a);
+    let (dl, el) = (Position::dot(d, d), Position::dot(e, e));
+    let scale = C::one() / ((C::one() + C::one()) * Position::cross(d, e));
+    Position::new((e.y * dl - d.y * el) * scale, (d.x * el - e.x * dl) * scale)
+}
+
+/// A monotonic function of the angle of (dx, dy), in [0, 1].
+fn _pseudo_angle<C: Coordinate>(dx: C, dy: C) -> C {
+    let p = dx / (dx.abs() + dy.abs());
+    let four = C::from(4).unwrap();
+    let angle = if dy > C::zero() {
+        (C::from(3).unwrap() - p) / four
+    } else {
+        (C::one() + p) / four
+    };
+    if angle.is_nan() {
+        C::zero()
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::algorithms::random::Lcg;
+    use crate::planar::types::MultiPoint;
+
+    fn to_positions(coords: Vec<(f64, f64)>) -> Vec<Position<f64>> {
+        coords.into_iter().map(Position::from).collect()
+    }
+
+    /// Check the triangles are counter-clockwise, linked, and Delaunay.
+    fn assert_is_delaunay(t: &DelaunayTriangulation<f64>) {
+        let p = |e: usize| t.positions[t.triangles[e]];
+        for e in 0..t.triangles.len() {
+            if e % 3 == 0 {
+                assert!(orient2d(p(e), p(e + 1), p(e + 2)) > 0.);
+            }
+            if let Some(opposite) = t.halfedges[e] {
+                assert_eq!(t.halfedges[opposite], Some(e));
+                assert_eq!(t.triangles[opposite], t.triangles[next_halfedge(e)]);
+                let far = p(prev_halfedge(opposite));
+                assert!(incircle(p(e), p(next_halfedge(e)), p(prev_halfedge(e)), far) <= 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn check_square() {
+        let positions = to_positions(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        let t = DelaunayTriangulation::new(&positions);
+        assert_eq!(t.num_triangles(), 2);
+        assert_eq!(t.hull.len(), 4);
+        assert_is_delaunay(&t);
+    }
+
+    #[test]
+    fn check_collinear() {
+        let positions = to_positions(vec![(1., 1.), (0., 0.), (2., 2.), (1., 1.)]);
+        let t = DelaunayTriangulation::new(&positions);
+        assert_eq!(t.num_triangles(), 0);
+        assert_eq!(t.hull, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn check_grid_with_duplicates() {
+        let mut coords = Vec::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                coords.push((i as f64, j as f64));
+            }
+        }
+        coords.push((5., 5.));
+        let t = DelaunayTriangulation::new(&to_positions(coords));
+        // A triangulation of n positions with h on the hull has 2n - h - 2 triangles.
+        assert_eq!(t.num_triangles(), 2 * 100 - 36 - 2);
+        assert_is_delaunay(&t);
+    }
+
+    #[test]
+    fn check_pseudo_random() {
+        let mut rng = Lcg::new(12345);
+        let mut next = || rng.next_below(1 << 20) as f64 / 10_000.;
+        let coords: Vec<(f64, f64)> = (0..500).map(|_| (next(), next())).collect();
+        let t = DelaunayTriangulation::new(&to_positions(coords));
+        assert_eq!(t.num_triangles(), 2 * 500 - t.hull.len() - 2);
+        assert_is_delaunay(&t);
+    }
+
+    #[test]
+    fn check_triangles_and_neighbors() {
+        let positions = to_positions(vec![(0., 0.), (2., 0.), (1., 1.), (1., -1.)]);
+        let t = DelaunayTriangulation::new(&positions);
+        let triangles = t.to_triangles();
+        assert_eq!(triangles.len(), 2);
+        assert!(triangles.iter().all(|t| t.signed_area() > 0.));
+        // The two triangles share the edge from (0, 0) to (2, 0).
+        let neighbors = t.triangle_neighbors();
+        assert_eq!(neighbors[0].iter().flatten().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(neighbors[1].iter().flatten().collect::<Vec<_>>(), vec![&0]);
+    }
+
+    #[test]
+    fn check_cocircular() {
+        // Positions on a circle are only cocircular up to rounding, which the
+        // exact predicates see.
+        let n = 64;
+        let coords: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI * 2. / n as f64;
+                (1e6 + angle.cos(), 1e6 + angle.sin())
+            })
+            .collect();
+        let t = DelaunayTriangulation::new(&to_positions(coords));
+        assert_eq!(t.hull.len(), n);
+        assert_eq!(t.num_triangles(), n - 2);
+    }
+
+    #[test]
+    fn check_multipoint() {
+        let mp = MultiPoint::from(vec![(0., 0.), (1., 0.), (0., 1.)]);
+        let t = mp.delaunay_triangulation();
+        assert_eq!(t.num_triangles(), 1);
+    }
+}
@@ -0,0 +1,262 @@
+use super::interior_point::find_interior_position;
+use super::normalize::_geometry_parts;
+use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
+use crate::planar::primitives::{
+    HasEnvelope, Position, SafePosition, Segment, SegmentIntersection,
+};
+use crate::planar::types::{Geometry, LineString, Polygon};
+use crate::Coordinate;
+use std::collections::HashSet;
+
+/**
+ * Check whether two geometries have the same type and structure, and each
+ * position is within `tolerance` of the corresponding position of the other.
+ * With a tolerance of zero, this is the same as `==`, except for NaNs.
+ */
+pub fn equals_exact<C: Coordinate>(
+    geometry_1: &Geometry<C>,
+    geometry_2: &Geometry<C>,
+    tolerance: C,
+) -> bool {
+    let (rank_1, parts_1) = _geometry_parts(geometry_1);
+    let (rank_2, parts_2) = _geometry_parts(geometry_2);
+    let is_close = |p: &Position<C>, q: &Position<C>| {
+        let offset = *p - *q;
+        Position::dot(offset, offset) <= tolerance * tolerance
+    };
+    rank_1 == rank_2
+        && parts_1.len() == parts_2.len()
+        && parts_1.iter().zip(&parts_2).all(|(rings_1, rings_2)| {
+            rings_1.len() == rings_2.len()
+                && rings_1.iter().zip(rings_2).all(|(ring_1, ring_2)| {
+                    ring_1.len() == ring_2.len()
+                        && ring_1
+                            .iter()
+                            .zip(ring_2.iter())
+                            .all(|(p, q)| is_close(p, q))
+                })
+        })
+}
+
+/**
+ * Check whether two geometries cover the same set of points, regardless of
+ * their types, the order of their parts and positions, repeated positions,
+ * or how their lines are split into segments.
+ *
+ * Geometries of different dimensions are never equal, unless both are empty.
+ * Lines are the same if every segment of each is covered by segments of the
+ * other.  Polygons are the same if their boundaries are the same, and an
+ * interior point of each polygon of each is inside the other.  The geometries
+ * should be valid.
+ *
+ * Segments only cover each other if they are exactly collinear, so splitting
+ * a segment at a position that isn't exactly on it changes the point set.
+ */
+pub fn equals_topo<C: Coordinate>(geometry_1: &Geometry<C>, geometry_2: &Geometry<C>) -> bool {
+    if geometry_1.is_empty() || geometry_2.is_empty() {
+        return geometry_1.is_empty() && geometry_2.is_empty();
+    }
+    if geometry_1.dimension() != geometry_2.dimension() {
+        return false;
+    }
+    match geometry_1.dimension() {
+        0 => match _position_set(geometry_1) {
+            Some(positions) => Some(positions) == _position_set(geometry_2),
+            None => false,
+        },
+        1 => {
+            let segments_1 = _segments(_line_strings(geometry_1));
+            let segments_2 = _segments(_line_strings(geometry_2));
+            _segments_cover(&segments_1, &segments_2) && _segments_cover(&segments_2, &segments_1)
+        }
+        _ => {
+            let (polygons_1, polygons_2) = (_polygons(geometry_1), _polygons(geometry_2));
+            let rings_1 = polygons_1.iter().flat_map(|p| _rings(p));
+            let rings_2 = polygons_2.iter().flat_map(|p| _rings(p));
+            let (segments_1, segments_2) = (_segments(rings_1), _segments(rings_2));
+            _segments_cover(&segments_1, &segments_2)
+                && _segments_cover(&segments_2, &segments_1)
+                && _interiors_inside(&polygons_1, &polygons_2)
+                && _interiors_inside(&polygons_2, &polygons_1)
+        }
+    }
+}
+
+/// The distinct positions of a 0-dimensional geometry, or None if any is NaN.
+fn _position_set<C: Coordinate>(geometry: &Geometry<C>) -> Option<HashSet<SafePosition<C>>> {
+    geometry
+        .coords_iter()
+        .map(|p| p.to_hashable().ok())
+        .collect()
+}
+
+fn _line_strings<C: Coordinate>(geometry: &Geometry<C>) -> Vec<&LineString<C>> {
+    match geometry {
+        Geometry::LineString(ls) => vec![ls],
+        Geometry::MultiLineString(mls) => mls.line_strings.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn _polygons<C: Coordinate>(geometry: &Geometry<C>) -> Vec<&Polygon<C>> {
+    match geometry {
+        Geometry::Polygon(p) => vec![p],
+        Geometry::MultiPolygon(mp) => mp.polygons.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn _rings<C: Coordinate>(polygon: &Polygon<C>) -> Vec<&LineString<C>> {
+    std::iter::once(&polygon.exterior)
+        .chain(&polygon.interiors)
+        .collect()
+}
+
+/// The segments of the linestrings, without zero-length ones.
+fn _segments<'a, C: Coordinate + 'a>(
+    linestrings: impl IntoIterator<Item = &'a LineString<C>>,
+) -> Vec<Segment<C>> {
+    linestrings
+        .into_iter()
+        .flat_map(|ls| ls.segments_iter())
+        .filter(|s| s.start != s.end)
+        .collect()
+}
+
+/**
+ * Check whether every segment is covered by the union of `others`, by
+ * sweeping along it through the parts that the others overlap.
+ */
+fn _segments_cover<C: Coordinate>(segments: &[Segment<C>], others: &[Segment<C>]) -> bool {
+    let rtree = Flatbush::new(others, FLATBUSH_DEFAULT_DEGREE);
+    segments.iter().all(|segment| {
+        let direction = segment.end - segment.start;
+        let length_2 = Position::dot(direction, direction);
+        let along = |p: Position<C>| Position::dot(p - segment.start, direction) / length_2;
+        let mut intervals: Vec<(C, C)> = rtree
+            .find_intersection_candidates(segment.envelope())
+            .into_iter()
+            .filter_map(|id| match segment.intersect_segment(others[id]) {
+                SegmentIntersection::Segment(overlap) => {
+                    let (t0, t1) = (along(overlap.start), along(overlap.end));
+                    Some((t0.min(t1), t0.max(t1)))
+                }
+                _ => None,
+            })
+            .collect();
+        intervals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mut reach = C::zero();
+        for (start, end) in intervals {
+            if start > reach {
+                break;
+            }
+            reach = reach.max(end);
+        }
+        reach >= C::one()
+    })
+}
+
+/// Check whether an interior point of each polygon is inside one of `others`.
+fn _interiors_inside<C: Coordinate>(polygons: &[&Polygon<C>], others: &[&Polygon<C>]) -> bool {
+    polygons
+        .iter()
+        .all(|polygon| match find_interior_position(polygon) {
+            Some((position, _)) => others.iter().any(|other| _is_inside(other, position)),
+            None => true,
+        })
+}
+
+fn _is_inside<C: Coordinate>(polygon: &Polygon<C>, position: Position<C>) -> bool {
+    let winding_number: i32 = _rings(polygon)
+        .into_iter()
+        .flat_map(|ring| ring.segments_iter())
+        .map(|segment| Segment::find_winding_number(position, segment))
+        .sum();
+    winding_number % 2 != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planar::types::{MultiLineString, MultiPoint, MultiPolygon, Point};
+
+    #[test]
+    fn check_equals_exact() {
+        let ls_1 = Geometry::from(LineString::from(vec![(0., 0.), (1., 1.)]));
+        let ls_2 = Geometry::from(LineString::from(vec![(0., 0.1), (1., 1.)]));
+        assert!(ls_1.equals_exact(&ls_1, 0.));
+        assert!(!ls_1.equals_exact(&ls_2, 0.05));
+        assert!(ls_1.equals_exact(&ls_2, 0.1));
+
+        let reversed = Geometry::from(LineString::from(vec![(1., 1.), (0., 0.)]));
+        assert!(!ls_1.equals_exact(&reversed, 0.1));
+        let mls = Geometry::from(MultiLineString::new(vec![LineString::from(vec![
+            (0., 0.),
+            (1., 1.),
+        ])]));
+        assert!(!ls_1.equals_exact(&mls, 0.1));
+    }
+
+    #[test]
+    fn check_equals_topo_points() {
+        let mp = Geometry::from(MultiPoint::from(vec![(1., 1.), (0., 0.), (1., 1.)]));
+        let mp_2 = Geometry::from(MultiPoint::from(vec![(0., 0.), (1., 1.)]));
+        assert!(mp.equals_topo(&mp_2));
+        let point = Geometry::from(Point::from((0., 0.)));
+        assert!(!mp.equals_topo(&point));
+        assert!(point.equals_topo(&Geometry::from(MultiPoint::from(vec![(0., 0.)]))));
+    }
+
+    #[test]
+    fn check_equals_topo_lines() {
+        let ls = Geometry::from(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]));
+        let split = Geometry::from(MultiLineString::new(vec![
+            LineString::from(vec![(2., 2.), (2., 1.), (2., 0.)]),
+            LineString::from(vec![(0., 0.), (1., 0.), (1., 0.), (2., 0.)]),
+        ]));
+        assert!(ls.equals_topo(&split));
+        assert!(split.equals_topo(&ls));
+        let shorter = Geometry::from(LineString::from(vec![(0., 0.), (2., 0.), (2., 1.)]));
+        assert!(!ls.equals_topo(&shorter));
+        assert!(!shorter.equals_topo(&ls));
+    }
+
+    #[test]
+    fn check_equals_topo_polygons() {
+        let square = Geometry::from(Polygon::from(vec![
+            (0., 0.),
+            (2., 0.),
+            (2., 2.),
+            (0., 2.),
+            (0., 0.),
+        ]));
+        let rotated = Geometry::from(Polygon::from(vec![
+            (2., 2.),
+            (2., 1.),
+            (2., 0.),
+            (0., 0.),
+            (0., 2.),
+            (2., 2.),
+        ]));
+        assert!(square.equals_topo(&rotated));
+        assert!(!square.equals_exact(&rotated, 0.));
+
+        // The same boundary, but the hole is filled in.
+        let holed = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (1., 2.),
+                (2., 2.),
+                (2., 1.),
+                (1., 1.),
+            ])],
+        );
+        let island = Polygon::from(vec![(1., 1.), (2., 1.), (2., 2.), (1., 2.), (1., 1.)]);
+        let filled = Geometry::from(MultiPolygon::new(vec![holed.clone(), island]));
+        let holed = Geometry::from(holed);
+        assert!(holed.equals_topo(&holed.normalize()));
+        assert!(!holed.equals_topo(&filled));
+        assert!(!filled.equals_topo(&holed));
+    }
+}
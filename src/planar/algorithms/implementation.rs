@@ -1,9 +1,879 @@
-use super::convex_hull::find_convex_hull_of_simple_loop;
-use crate::planar::types::{LineString, Polygon};
+use super::centroid::CentroidAccumulator;
+use super::concave_hull::find_concave_hull;
+use super::convex_hull::find_convex_hull;
+use super::coverage::simplify_coverage;
+use super::delaunay::DelaunayTriangulation;
+use super::equality::{equals_exact, equals_topo};
+use super::interior_point::find_interior_position;
+use super::make_valid::{
+    make_valid_lines, make_valid_lines_with_precision, make_valid_points, make_valid_polygons,
+    make_valid_polygons_with_precision, FillRule,
+};
+use super::minimum_bounding::{find_minimum_bounding_circle, find_minimum_rotated_rectangle};
+use super::normalize::{
+    normalize_line_string, normalize_multi_line_string, normalize_multi_point,
+    normalize_multi_polygon, normalize_polygon,
+};
+use super::offset_curve::find_offset_curve;
+use super::polylabel::find_pole_of_inaccessibility;
+use super::preserve_topology::simplify_lines_preserve_topology;
+use super::simplify::{simplify_rdp, simplify_vw, MIN_LINE_POSITIONS, MIN_RING_POSITIONS};
+use super::triangulate::{triangulate_polygon, triangulate_polygon_ear_clipping};
+use super::voronoi::find_voronoi_cells;
+use crate::planar::primitives::{AffineTransform, Envelope, Position, PrecisionModel, Triangle};
+use crate::planar::types::{
+    Empty, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
 use crate::Coordinate;
 
+// Convex hulls of all positions.  See `find_convex_hull` for details.
+
+impl<C: Coordinate> MultiPoint<C> {
+    pub fn convex_hull(&self) -> Geometry<C> {
+        find_convex_hull(self.points.iter().map(|p| p.0))
+    }
+}
+
+impl<C: Coordinate> LineString<C> {
+    pub fn convex_hull(&self) -> Geometry<C> {
+        find_convex_hull(self.positions.iter().copied())
+    }
+}
+
+impl<C: Coordinate> MultiLineString<C> {
+    pub fn convex_hull(&self) -> Geometry<C> {
+        find_convex_hull(
+            self.line_strings
+                .iter()
+                .flat_map(|ls| ls.positions.iter().copied()),
+        )
+    }
+}
+
+impl<C: Coordinate> Polygon<C> {
+    /// Holes can't contribute to the hull, so only the exterior is used.
+    pub fn convex_hull(&self) -> Geometry<C> {
+        find_convex_hull(self.exterior.positions.iter().copied())
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    /// Holes can't contribute to the hull, so only exteriors are used.
+    pub fn convex_hull(&self) -> Geometry<C> {
+        find_convex_hull(
+            self.polygons
+                .iter()
+                .flat_map(|p| p.exterior.positions.iter().copied()),
+        )
+    }
+}
+
+impl<C: Coordinate> Geometry<C> {
+    pub fn convex_hull(&self) -> Geometry<C> {
+        match self {
+            Geometry::Empty(_) => Geometry::empty(),
+            Geometry::Point(x) => Geometry::from(x.clone()),
+            Geometry::MultiPoint(x) => x.convex_hull(),
+            Geometry::LineString(x) => x.convex_hull(),
+            Geometry::MultiLineString(x) => x.convex_hull(),
+            Geometry::Polygon(x) => x.convex_hull(),
+            Geometry::MultiPolygon(x) => x.convex_hull(),
+        }
+    }
+}
+
+impl<C: Coordinate> LineString<C> {
+    /**
+     * Offset the LineString by `distance` to one side.
+     *
+     * Positive distances offset to the left, negative to the right.
+     * See `find_offset_curve` for details.
+     */
+    pub fn offset_curve(&self, distance: C) -> LineString<C> {
+        find_offset_curve(self, distance)
+    }
+}
+
+// Simplification
+
+/// A simplification algorithm applied to a single sequence of positions.
+type Simplifier<C> = fn(&[Position<C>], C, usize) -> Vec<Position<C>>;
+
+fn _simplify_linestring<C: Coordinate>(
+    linestring: &LineString<C>,
+    simplifier: Simplifier<C>,
+    threshold: C,
+) -> LineString<C> {
+    let min_positions = if linestring.is_closed() {
+        MIN_RING_POSITIONS
+    } else {
+        MIN_LINE_POSITIONS
+    };
+    LineString::new(simplifier(&linestring.positions, threshold, min_positions))
+}
+
+fn _simplify_ring<C: Coordinate>(
+    ring: &LineString<C>,
+    simplifier: Simplifier<C>,
+    threshold: C,
+) -> LineString<C> {
+    LineString::new(simplifier(&ring.positions, threshold, MIN_RING_POSITIONS))
+}
+
+fn _simplify_polygon<C: Coordinate>(
+    polygon: &Polygon<C>,
+    simplifier: Simplifier<C>,
+    threshold: C,
+) -> Polygon<C> {
+    Polygon::new(
+        _simplify_ring(&polygon.exterior, simplifier, threshold),
+        polygon
+            .interiors
+            .iter()
+            .map(|ring| _simplify_ring(ring, simplifier, threshold))
+            .collect(),
+    )
+}
+
+fn _simplify_geometry<C: Coordinate>(
+    geometry: &Geometry<C>,
+    simplifier: Simplifier<C>,
+    threshold: C,
+) -> Geometry<C> {
+    match geometry {
+        Geometry::Empty(_) | Geometry::Point(_) | Geometry::MultiPoint(_) => geometry.clone(),
+        Geometry::LineString(ls) => _simplify_linestring(ls, simplifier, threshold).into(),
+        Geometry::MultiLineString(mls) => {
+            _simplify_multilinestring(mls, simplifier, threshold).into()
+        }
+        Geometry::Polygon(p) => _simplify_polygon(p, simplifier, threshold).into(),
+        Geometry::MultiPolygon(mp) => _simplify_multipolygon(mp, simplifier, threshold).into(),
+    }
+}
+
+macro_rules! impl_simplify {
+    // Implement `simplify` and `simplify_vw` for a type, in terms of a
+    // function that simplifies it with a given Simplifier.
+    ($type:ident, $func:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /**
+             * Simplify with the Ramer–Douglas–Peucker algorithm.
+             *
+             * Positions within `tolerance` of the simplified line are removed.
+             * Rings stay closed, and keep at least 4 positions.
+             */
+            pub fn simplify(&self, tolerance: C) -> $type<C> {
+                $func(self, simplify_rdp, tolerance)
+            }
+
+            /**
+             * Simplify with the Visvalingam–Whyatt algorithm.
+             *
+             * Positions whose effective area is less than `area_threshold`
+             * are removed.  Rings stay closed, and keep at least 4 positions.
+             */
+            pub fn simplify_vw(&self, area_threshold: C) -> $type<C> {
+                $func(self, simplify_vw, area_threshold)
+            }
+        }
+    };
+}
+
+fn _simplify_multilinestring<C: Coordinate>(
+    mls: &MultiLineString<C>,
+    simplifier: Simplifier<C>,
+    threshold: C,
+) -> MultiLineString<C> {
+    MultiLineString::new(
+        mls.line_strings
+            .iter()
+            .map(|ls| _simplify_linestring(ls, simplifier, threshold))
+            .collect(),
+    )
+}
+
+fn _simplify_multipolygon<C: Coordinate>(
+    mp: &MultiPolygon<C>,
+    simplifier: Simplifier<C>,
+    threshold: C,
+) -> MultiPolygon<C> {
+    MultiPolygon::new(
+        mp.polygons
+            .iter()
+            .map(|p| _simplify_polygon(p, simplifier, threshold))
+            .collect(),
+    )
+}
+
+impl_simplify!(LineString, _simplify_linestring);
+impl_simplify!(MultiLineString, _simplify_multilinestring);
+impl_simplify!(Polygon, _simplify_polygon);
+impl_simplify!(MultiPolygon, _simplify_multipolygon);
+impl_simplify!(Geometry, _simplify_geometry);
+
+// Topology-preserving simplification
+
+/// Simplify the rings of all the polygons together, keeping their order.
+fn _simplify_polygons_preserve_topology<C: Coordinate>(
+    polygons: &[Polygon<C>],
+    tolerance: C,
+) -> Vec<Polygon<C>> {
+    let rings: Vec<&[Position<C>]> = polygons
+        .iter()
+        .flat_map(|p| std::iter::once(&p.exterior).chain(p.interiors.iter()))
+        .map(|ring| &ring.positions[..])
+        .collect();
+    let min_positions = vec![MIN_RING_POSITIONS; rings.len()];
+    let mut simplified = simplify_lines_preserve_topology(&rings, &min_positions, tolerance)
+        .into_iter()
+        .map(LineString::new);
+    polygons
+        .iter()
+        .map(|p| {
+            let exterior = simplified.next().unwrap();
+            let interiors = simplified.by_ref().take(p.interiors.len()).collect();
+            Polygon::new(exterior, interiors)
+        })
+        .collect()
+}
+
+/// Simplify all the linestrings together, keeping their order.
+fn _simplify_linestrings_preserve_topology<C: Coordinate>(
+    linestrings: &[LineString<C>],
+    tolerance: C,
+) -> Vec<LineString<C>> {
+    let lines: Vec<&[Position<C>]> = linestrings.iter().map(|ls| &ls.positions[..]).collect();
+    let min_positions: Vec<usize> = linestrings
+        .iter()
+        .map(|ls| {
+            if ls.is_closed() {
+                MIN_RING_POSITIONS
+            } else {
+                MIN_LINE_POSITIONS
+            }
+        })
+        .collect();
+    simplify_lines_preserve_topology(&lines, &min_positions, tolerance)
+        .into_iter()
+        .map(LineString::new)
+        .collect()
+}
+
+fn _simplify_linestring_preserve_topology<C: Coordinate>(
+    ls: &LineString<C>,
+    tolerance: C,
+) -> LineString<C> {
+    _simplify_linestrings_preserve_topology(std::slice::from_ref(ls), tolerance).remove(0)
+}
+
+fn _simplify_multilinestring_preserve_topology<C: Coordinate>(
+    mls: &MultiLineString<C>,
+    tolerance: C,
+) -> MultiLineString<C> {
+    MultiLineString::new(_simplify_linestrings_preserve_topology(
+        &mls.line_strings,
+        tolerance,
+    ))
+}
+
+fn _simplify_polygon_preserve_topology<C: Coordinate>(
+    polygon: &Polygon<C>,
+    tolerance: C,
+) -> Polygon<C> {
+    _simplify_polygons_preserve_topology(std::slice::from_ref(polygon), tolerance).remove(0)
+}
+
+fn _simplify_multipolygon_preserve_topology<C: Coordinate>(
+    mp: &MultiPolygon<C>,
+    tolerance: C,
+) -> MultiPolygon<C> {
+    MultiPolygon::new(_simplify_polygons_preserve_topology(
+        &mp.polygons,
+        tolerance,
+    ))
+}
+
+macro_rules! impl_simplify_preserve_topology {
+    // Implement `simplify_preserve_topology` for a type, in terms of a
+    // function that simplifies it.
+    ($type:ident, $func:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /**
+             * Simplify with Ramer–Douglas–Peucker, without changing topology.
+             *
+             * Positions within `tolerance` of the simplified line are removed,
+             * unless removing them would introduce an intersection, or move
+             * a part to the other side of another (eg, a hole out of its
+             * shell).  If the input is valid, the output is valid.  If the
+             * input is invalid and so is its simplification, the input is
+             * returned unchanged.
+             */
+            pub fn simplify_preserve_topology(&self, tolerance: C) -> $type<C> {
+                let simplified = $func(self, tolerance);
+                if simplified.validate().is_ok() {
+                    simplified
+                } else {
+                    self.clone()
+                }
+            }
+        }
+    };
+}
+
+impl_simplify_preserve_topology!(LineString, _simplify_linestring_preserve_topology);
+impl_simplify_preserve_topology!(MultiLineString, _simplify_multilinestring_preserve_topology);
+impl_simplify_preserve_topology!(Polygon, _simplify_polygon_preserve_topology);
+impl_simplify_preserve_topology!(MultiPolygon, _simplify_multipolygon_preserve_topology);
+
+impl<C: Coordinate> Geometry<C> {
+    /// Simplify without changing topology; see `Polygon::simplify_preserve_topology`.
+    pub fn simplify_preserve_topology(&self, tolerance: C) -> Geometry<C> {
+        match self {
+            Geometry::Empty(_) | Geometry::Point(_) | Geometry::MultiPoint(_) => self.clone(),
+            Geometry::LineString(x) => x.simplify_preserve_topology(tolerance).into(),
+            Geometry::MultiLineString(x) => x.simplify_preserve_topology(tolerance).into(),
+            Geometry::Polygon(x) => x.simplify_preserve_topology(tolerance).into(),
+            Geometry::MultiPolygon(x) => x.simplify_preserve_topology(tolerance).into(),
+        }
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    /**
+     * Simplify the polygons as a coverage, keeping shared borders consistent.
+     *
+     * Borders shared between polygons are simplified once, so neighbors
+     * still match exactly.  See `simplify_coverage` for details.
+     */
+    pub fn simplify_coverage(&self, tolerance: C) -> MultiPolygon<C> {
+        MultiPolygon::new(simplify_coverage(&self.polygons, tolerance))
+    }
+}
+
+// Centroids
+
+macro_rules! impl_centroid {
+    // Implement `centroid` for a type, with the CentroidAccumulator method
+    // that adds that type.
+    ($type:ident, $add:ident, $doc:expr) => {
+        impl<C: Coordinate> $type<C> {
+            #[doc = $doc]
+            ///
+            /// If the geometry is empty, return None.
+            pub fn centroid(&self) -> Option<Point<C>> {
+                let mut accumulator = CentroidAccumulator::new();
+                accumulator.$add(self);
+                accumulator.centroid()
+            }
+        }
+    };
+}
+
+impl_centroid!(Point, add_point, "The centroid of a Point is itself.");
+impl_centroid!(
+    MultiPoint,
+    add_multipoint,
+    "The centroid of a MultiPoint is the mean of its points."
+);
+impl_centroid!(
+    LineString,
+    add_linestring,
+    "The centroid of a LineString is the mean of its segment midpoints, weighted by length."
+);
+impl_centroid!(
+    MultiLineString,
+    add_multilinestring,
+    "The centroid of a MultiLineString is the length-weighted centroid of its LineStrings."
+);
+impl_centroid!(
+    Polygon,
+    add_polygon,
+    "The centroid of a Polygon is its center of mass, excluding the holes."
+);
+impl_centroid!(
+    MultiPolygon,
+    add_multipolygon,
+    "The centroid of a MultiPolygon is the area-weighted centroid of its Polygons."
+);
+impl_centroid!(
+    Geometry,
+    add_geometry,
+    "Find the centroid of the highest dimension components of the Geometry."
+);
+
+impl<C: Coordinate> Empty<C> {
+    pub fn centroid(&self) -> Option<Point<C>> {
+        None
+    }
+}
+
+// Interior points
+
+impl<C: Coordinate> Polygon<C> {
+    /**
+     * Find a point strictly in the interior of the Polygon (not on its boundary).
+     * See `find_interior_position` for details.  If empty, return None.
+     */
+    pub fn point_on_surface(&self) -> Option<Point<C>> {
+        find_interior_position(self).map(|(position, _)| Point(position))
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    /**
+     * Find a point strictly in the interior of one of the Polygons.
+     * The Polygon with the widest interior scanline interval is used.
+     * If empty, return None.
+     */
+    pub fn point_on_surface(&self) -> Option<Point<C>> {
+        self.polygons
+            .iter()
+            .filter_map(find_interior_position)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(position, _)| Point(position))
+    }
+}
+
+// Poles of inaccessibility
+
 impl<C: Coordinate> Polygon<C> {
-    pub fn convex_hull(&self) -> LineString<C> {
-        find_convex_hull_of_simple_loop(&self.exterior)
+    /**
+     * Find the interior point farthest from the boundary, and its distance,
+     * to within `precision`.  This is a good anchor for a label.
+     * See `find_pole_of_inaccessibility` for details.  If empty, return None.
+     */
+    pub fn pole_of_inaccessibility(&self, precision: C) -> Option<(Point<C>, C)> {
+        find_pole_of_inaccessibility(std::slice::from_ref(self), precision)
+            .map(|(position, distance)| (Point(position), distance))
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    /**
+     * Find the interior point farthest from the boundary of any Polygon, and
+     * its distance, to within `precision`.  If empty, return None.
+     */
+    pub fn pole_of_inaccessibility(&self, precision: C) -> Option<(Point<C>, C)> {
+        find_pole_of_inaccessibility(&self.polygons, precision)
+            .map(|(position, distance)| (Point(position), distance))
+    }
+}
+
+// Concave hulls of all positions.  See `find_concave_hull` for details.
+
+impl<C: Coordinate> MultiPoint<C> {
+    pub fn concave_hull(&self, ratio: C) -> Geometry<C> {
+        find_concave_hull(self.points.iter().map(|p| p.0), ratio)
+    }
+}
+
+impl<C: Coordinate> LineString<C> {
+    pub fn concave_hull(&self, ratio: C) -> Geometry<C> {
+        find_concave_hull(self.positions.iter().copied(), ratio)
+    }
+}
+
+impl<C: Coordinate> MultiLineString<C> {
+    pub fn concave_hull(&self, ratio: C) -> Geometry<C> {
+        find_concave_hull(
+            self.line_strings
+                .iter()
+                .flat_map(|ls| ls.positions.iter().copied()),
+            ratio,
+        )
+    }
+}
+
+impl<C: Coordinate> Polygon<C> {
+    pub fn concave_hull(&self, ratio: C) -> Geometry<C> {
+        find_concave_hull(self.exterior.positions.iter().copied(), ratio)
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    pub fn concave_hull(&self, ratio: C) -> Geometry<C> {
+        find_concave_hull(
+            self.polygons
+                .iter()
+                .flat_map(|p| p.exterior.positions.iter().copied()),
+            ratio,
+        )
+    }
+}
+
+impl<C: Coordinate> Geometry<C> {
+    pub fn concave_hull(&self, ratio: C) -> Geometry<C> {
+        match self {
+            Geometry::Empty(_) => Geometry::empty(),
+            Geometry::Point(x) => Geometry::from(x.clone()),
+            Geometry::MultiPoint(x) => x.concave_hull(ratio),
+            Geometry::LineString(x) => x.concave_hull(ratio),
+            Geometry::MultiLineString(x) => x.concave_hull(ratio),
+            Geometry::Polygon(x) => x.concave_hull(ratio),
+            Geometry::MultiPolygon(x) => x.concave_hull(ratio),
+        }
+    }
+}
+
+// Delaunay triangulation
+
+impl<C: Coordinate> MultiPoint<C> {
+    /// Triangulate the points.  See `DelaunayTriangulation` for details.
+    pub fn delaunay_triangulation(&self) -> DelaunayTriangulation<C> {
+        let positions: Vec<Position<C>> = self.points.iter().map(|p| p.0).collect();
+        DelaunayTriangulation::new(&positions)
+    }
+
+    /**
+     * Find the Voronoi cell of each point, in the same order, clipped to the
+     * envelope.  See `find_voronoi_cells` for details.
+     */
+    pub fn voronoi_diagram(&self, envelope: Envelope<C>) -> Vec<Polygon<C>> {
+        let positions: Vec<Position<C>> = self.points.iter().map(|p| p.0).collect();
+        find_voronoi_cells(&positions, envelope)
+    }
+}
+
+// Polygon triangulation
+
+impl<C: Coordinate> Polygon<C> {
+    /**
+     * Triangulate the Polygon, with the holes excluded, using the ring edges
+     * and otherwise the best-shaped triangles.
+     * See `triangulate_polygon` for details.
+     */
+    pub fn triangulate(&self) -> Vec<Triangle<C>> {
+        triangulate_polygon(self)
+    }
+
+    /**
+     * Triangulate the Polygon quickly, with the holes excluded, but with no
+     * attention to the shape of the triangles.
+     * See `triangulate_polygon_ear_clipping` for details.
+     */
+    pub fn triangulate_ear_clipping(&self) -> Vec<Triangle<C>> {
+        triangulate_polygon_ear_clipping(self)
+    }
+}
+
+// Minimum bounding circles and rectangles
+
+fn _point_positions<C: Coordinate>(point: &Point<C>) -> Vec<Position<C>> {
+    vec![point.0]
+}
+
+fn _multipoint_positions<C: Coordinate>(multipoint: &MultiPoint<C>) -> Vec<Position<C>> {
+    multipoint.points.iter().map(|p| p.0).collect()
+}
+
+fn _linestring_positions<C: Coordinate>(linestring: &LineString<C>) -> Vec<Position<C>> {
+    linestring.positions.clone()
+}
+
+fn _multilinestring_positions<C: Coordinate>(
+    multilinestring: &MultiLineString<C>,
+) -> Vec<Position<C>> {
+    multilinestring
+        .line_strings
+        .iter()
+        .flat_map(|ls| ls.positions.iter().copied())
+        .collect()
+}
+
+/// Holes are inside the exterior, so they can't affect any bounding shape.
+fn _polygon_positions<C: Coordinate>(polygon: &Polygon<C>) -> Vec<Position<C>> {
+    polygon.exterior.positions.clone()
+}
+
+fn _multipolygon_positions<C: Coordinate>(multipolygon: &MultiPolygon<C>) -> Vec<Position<C>> {
+    multipolygon
+        .polygons
+        .iter()
+        .flat_map(|p| p.exterior.positions.iter().copied())
+        .collect()
+}
+
+fn _geometry_positions<C: Coordinate>(geometry: &Geometry<C>) -> Vec<Position<C>> {
+    match geometry {
+        Geometry::Empty(_) => Vec::new(),
+        Geometry::Point(x) => _point_positions(x),
+        Geometry::MultiPoint(x) => _multipoint_positions(x),
+        Geometry::LineString(x) => _linestring_positions(x),
+        Geometry::MultiLineString(x) => _multilinestring_positions(x),
+        Geometry::Polygon(x) => _polygon_positions(x),
+        Geometry::MultiPolygon(x) => _multipolygon_positions(x),
+    }
+}
+
+macro_rules! impl_minimum_bounding {
+    // Implement the minimum bounding shapes for a type, with the function
+    // collecting its (outer) positions.
+    ($type:ident, $positions:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /**
+             * Find the smallest circle containing the geometry, as its center
+             * and radius.  See `find_minimum_bounding_circle` for details.
+             * If empty, return None.
+             */
+            pub fn minimum_bounding_circle(&self) -> Option<(Point<C>, C)> {
+                find_minimum_bounding_circle($positions(self))
+                    .map(|(center, radius)| (Point(center), radius))
+            }
+
+            /**
+             * Find the (possibly rotated) rectangle of least area containing
+             * the geometry.  See `find_minimum_rotated_rectangle` for details.
+             * If empty, return None.
+             */
+            pub fn minimum_rotated_rectangle(&self) -> Option<Polygon<C>> {
+                find_minimum_rotated_rectangle($positions(self))
+            }
+        }
+    };
+}
+
+impl_minimum_bounding!(Point, _point_positions);
+impl_minimum_bounding!(MultiPoint, _multipoint_positions);
+impl_minimum_bounding!(LineString, _linestring_positions);
+impl_minimum_bounding!(MultiLineString, _multilinestring_positions);
+impl_minimum_bounding!(Polygon, _polygon_positions);
+impl_minimum_bounding!(MultiPolygon, _multipolygon_positions);
+impl_minimum_bounding!(Geometry, _geometry_positions);
+
+impl<C: Coordinate> Empty<C> {
+    pub fn minimum_bounding_circle(&self) -> Option<(Point<C>, C)> {
+        None
+    }
+
+    pub fn minimum_rotated_rectangle(&self) -> Option<Polygon<C>> {
+        None
+    }
+}
+
+// Affine transformations
+
+macro_rules! impl_transform {
+    ($type:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /// Transform every position, keeping the geometry type.
+            pub fn transform(&self, transform: &AffineTransform<C>) -> $type<C> {
+                self.map_coords(|p| transform.apply(p))
+            }
+        }
+    };
+}
+
+impl_transform!(Empty);
+impl_transform!(Point);
+impl_transform!(MultiPoint);
+impl_transform!(LineString);
+impl_transform!(MultiLineString);
+impl_transform!(Polygon);
+impl_transform!(MultiPolygon);
+impl_transform!(Geometry);
+
+// Precision models
+
+macro_rules! impl_snap_to_grid {
+    ($type:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /**
+             * Round every position to the precision model, keeping the
+             * geometry type.  Nothing is removed, so the result may have
+             * repeated positions, or rings that collapse or touch; use
+             * `Geometry::make_valid_with_precision` to fix these.
+             */
+            pub fn snap_to_grid(&self, precision_model: &PrecisionModel<C>) -> $type<C> {
+                self.map_coords(|p| precision_model.make_precise_position(p))
+            }
+        }
+    };
+}
+
+impl_snap_to_grid!(Empty);
+impl_snap_to_grid!(Point);
+impl_snap_to_grid!(MultiPoint);
+impl_snap_to_grid!(LineString);
+impl_snap_to_grid!(MultiLineString);
+impl_snap_to_grid!(Polygon);
+impl_snap_to_grid!(MultiPolygon);
+impl_snap_to_grid!(Geometry);
+
+// Making geometries valid
+
+impl<C: Coordinate> Point<C> {
+    /// Return the point, or Empty if it has a non-finite coordinate.
+    pub fn make_valid(&self) -> Geometry<C> {
+        match self.validate() {
+            Ok(()) => Geometry::from(self.clone()),
+            Err(_) => Geometry::empty(),
+        }
+    }
+}
+
+impl<C: Coordinate> MultiPoint<C> {
+    /// Remove points with non-finite coordinates, and duplicate points.
+    pub fn make_valid(&self) -> Geometry<C> {
+        Geometry::from(make_valid_points(&self.points))
+    }
+}
+
+impl<C: Coordinate> LineString<C> {
+    /**
+     * Make a valid geometry covering the linestring.  If it's valid, it is
+     * returned unchanged; otherwise see `make_valid_lines`.
+     */
+    pub fn make_valid(&self) -> Geometry<C> {
+        match self.validate() {
+            Ok(()) => Geometry::from(self.clone()),
+            Err(_) => make_valid_lines(std::slice::from_ref(self)),
+        }
+    }
+}
+
+impl<C: Coordinate> MultiLineString<C> {
+    /**
+     * Make a valid geometry covering the linestrings.  If they're valid,
+     * they are returned unchanged; otherwise see `make_valid_lines`.
+     */
+    pub fn make_valid(&self) -> Geometry<C> {
+        match self.validate() {
+            Ok(()) => Geometry::from(self.clone()),
+            Err(_) => make_valid_lines(&self.line_strings),
+        }
+    }
+}
+
+impl<C: Coordinate> Polygon<C> {
+    /**
+     * Make a valid geometry covering the polygon, with `rule` deciding what
+     * self-intersecting rings enclose.  If it's valid, it is returned
+     * unchanged; otherwise see `make_valid_polygons`.
+     */
+    pub fn make_valid(&self, rule: FillRule) -> Geometry<C> {
+        match self.validate() {
+            Ok(()) => Geometry::from(self.clone()),
+            Err(_) => make_valid_polygons(std::slice::from_ref(self), rule),
+        }
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    /**
+     * Make a valid geometry covering the polygons, with `rule` deciding what
+     * overlapping and self-intersecting rings enclose.  If they're valid,
+     * they are returned unchanged; otherwise see `make_valid_polygons`.
+     */
+    pub fn make_valid(&self, rule: FillRule) -> Geometry<C> {
+        match self.validate() {
+            Ok(()) => Geometry::from(self.clone()),
+            Err(_) => make_valid_polygons(&self.polygons, rule),
+        }
+    }
+}
+
+impl<C: Coordinate> Geometry<C> {
+    /// Make the geometry valid; `rule` is only used for polygons.
+    pub fn make_valid(&self, rule: FillRule) -> Geometry<C> {
+        match self {
+            Geometry::Empty(_) => self.clone(),
+            Geometry::Point(x) => x.make_valid(),
+            Geometry::MultiPoint(x) => x.make_valid(),
+            Geometry::LineString(x) => x.make_valid(),
+            Geometry::MultiLineString(x) => x.make_valid(),
+            Geometry::Polygon(x) => x.make_valid(rule),
+            Geometry::MultiPolygon(x) => x.make_valid(rule),
+        }
+    }
+
+    /**
+     * Round the geometry to the precision model, and then make it valid.
+     * Any positions computed while making it valid, like the crossings of
+     * self-intersecting rings, are rounded too, so the result is on the grid.
+     */
+    pub fn make_valid_with_precision(
+        &self,
+        rule: FillRule,
+        precision_model: &PrecisionModel<C>,
+    ) -> Geometry<C> {
+        let snapped = self.snap_to_grid(precision_model);
+        if snapped.validate().is_ok() {
+            return snapped;
+        }
+        match &snapped {
+            Geometry::LineString(x) => {
+                make_valid_lines_with_precision(std::slice::from_ref(x), precision_model)
+            }
+            Geometry::MultiLineString(x) => {
+                make_valid_lines_with_precision(&x.line_strings, precision_model)
+            }
+            Geometry::Polygon(x) => {
+                make_valid_polygons_with_precision(std::slice::from_ref(x), rule, precision_model)
+            }
+            Geometry::MultiPolygon(x) => {
+                make_valid_polygons_with_precision(&x.polygons, rule, precision_model)
+            }
+            _ => snapped.make_valid(rule),
+        }
+    }
+}
+
+// Normalization.  See the functions in `normalize` for details.
+
+macro_rules! impl_normalize {
+    ($type:ident, $func:expr) => {
+        impl<C: Coordinate> $type<C> {
+            /**
+             * Put the geometry in a normal form, with rings oriented and
+             * rotated and the parts sorted, so that geometries with the same
+             * shape compare equal.
+             */
+            pub fn normalize(&self) -> $type<C> {
+                $func(self)
+            }
+        }
+    };
+}
+
+impl_normalize!(Empty, Empty::clone);
+impl_normalize!(Point, Point::clone);
+impl_normalize!(MultiPoint, normalize_multi_point);
+impl_normalize!(LineString, normalize_line_string);
+impl_normalize!(MultiLineString, normalize_multi_line_string);
+impl_normalize!(Polygon, normalize_polygon);
+impl_normalize!(MultiPolygon, normalize_multi_polygon);
+
+impl<C: Coordinate> Geometry<C> {
+    /// Put the geometry in a normal form.  See `NormalizedGeometry` for keys.
+    pub fn normalize(&self) -> Geometry<C> {
+        match self {
+            Geometry::Empty(x) => Geometry::from(x.normalize()),
+            Geometry::Point(x) => Geometry::from(x.normalize()),
+            Geometry::MultiPoint(x) => Geometry::from(x.normalize()),
+            Geometry::LineString(x) => Geometry::from(x.normalize()),
+            Geometry::MultiLineString(x) => Geometry::from(x.normalize()),
+            Geometry::Polygon(x) => Geometry::from(x.normalize()),
+            Geometry::MultiPolygon(x) => Geometry::from(x.normalize()),
+        }
+    }
+}
+
+// Equality.  See the functions in `equality` for details.
+
+impl<C: Coordinate> Geometry<C> {
+    /**
+     * Whether the geometries have the same type and structure, with each
+     * position within `tolerance` of the other's.
+     */
+    pub fn equals_exact(&self, other: &Geometry<C>, tolerance: C) -> bool {
+        equals_exact(self, other, tolerance)
+    }
+
+    /**
+     * Whether the geometries cover the same points, ignoring the order of
+     * parts and positions, repeated positions, and how lines are split up.
+     */
+    pub fn equals_topo(&self, other: &Geometry<C>) -> bool {
+        equals_topo(self, other)
     }
 }
@@ -0,0 +1,141 @@
+use crate::planar::primitives::{Envelope, HasEnvelope, Position};
+use crate::planar::types::Polygon;
+use crate::Coordinate;
+
+/**
+ * Find a position strictly in the interior of a polygon.
+ *
+ * This takes a horizontal scanline near the middle of the envelope, chosen
+ * to avoid all of the vertices, and intersects it with every ring segment.
+ * Sorted by x, consecutive pairs of crossings bound the intervals of the
+ * scanline that are inside the polygon.  The midpoint of the widest interval
+ * is returned, along with the width of that interval.
+ *
+ * If the polygon is empty or has no area (eg, all vertices have the same y),
+ * return None.  If the polygon is invalid, the result may not be interior.
+ */
+pub fn find_interior_position<C: Coordinate>(polygon: &Polygon<C>) -> Option<(Position<C>, C)> {
+    let center = match polygon.envelope() {
+        Envelope::Empty => return None,
+        Envelope::Bounds { min, max } => (min.y + max.y) / (C::one() + C::one()),
+    };
+    let scan_y = _find_scan_y(polygon, center)?;
+
+    let mut crossings: Vec<C> = std::iter::once(&polygon.exterior)
+        .chain(polygon.interiors.iter())
+        .flat_map(|ring| ring.segments_iter())
+        .filter(|s| (s.start.y < scan_y) != (s.end.y < scan_y))
+        .map(|s| s.start.x + (scan_y - s.start.y) * (s.end.x - s.start.x) / (s.end.y - s.start.y))
+        .collect();
+    crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    crossings
+        .chunks_exact(2)
+        .map(|pair| (pair[1] - pair[0], pair[0], pair[1]))
+        .filter(|(width, _, _)| *width > C::zero())
+        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+        .map(|(width, left, right)| {
+            let x = (left + right) / (C::one() + C::one());
+            (Position::new(x, scan_y), width)
+        })
+}
+
+/**
+ * Find a y value near `center` that no vertex of the polygon has.
+ *
+ * This is halfway between the nearest vertex y values at or below, and
+ * strictly above, the center.
+ */
+fn _find_scan_y<C: Coordinate>(polygon: &Polygon<C>, center: C) -> Option<C> {
+    let mut below: Option<C> = None;
+    let mut above: Option<C> = None;
+    let positions = std::iter::once(&polygon.exterior)
+        .chain(polygon.interiors.iter())
+        .flat_map(|ring| ring.positions.iter());
+    for position in positions {
+        let y = position.y;
+        if y <= center {
+            below = Some(below.map_or(y, |b| b.max(y)));
+        } else {
+            above = Some(above.map_or(y, |a| a.min(y)));
+        }
+    }
+    Some((below? + above?) / (C::one() + C::one()))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::types::{LineString, MultiPolygon, Point};
+
+    #[test]
+    fn check_empty() {
+        let polygon: Polygon<f64> = Polygon::from(LineString::new(Vec::new()));
+        assert_eq!(find_interior_position(&polygon), None);
+    }
+
+    #[test]
+    fn check_square() {
+        let polygon = Polygon::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]);
+        assert_eq!(
+            find_interior_position(&polygon),
+            Some((Position::new(2., 2.), 4.))
+        );
+    }
+
+    #[test]
+    fn check_hole_at_center() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (2., 2.),
+                (7., 2.),
+                (7., 8.),
+                (2., 8.),
+                (2., 2.),
+            ])],
+        );
+        // The scanline crosses at 0, 2, 7, 10: the widest inside interval is [7, 10].
+        let (position, width) = find_interior_position(&polygon).unwrap();
+        assert_eq!(position.x, 8.5);
+        assert_eq!(width, 3.);
+    }
+
+    #[test]
+    fn check_concave_u_shape() {
+        // The center of the envelope is outside the polygon.
+        let polygon = Polygon::from(vec![
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (8., 10.),
+            (8., 2.),
+            (2., 2.),
+            (2., 10.),
+            (0., 10.),
+            (0., 0.),
+        ]);
+        let (position, width) = find_interior_position(&polygon).unwrap();
+        assert_eq!(width, 2.);
+        assert!(position.x == 1. || position.x == 9.);
+        assert!(position.y > 2. && position.y < 10.);
+    }
+
+    #[test]
+    fn check_vertex_at_center_height() {
+        let polygon = Polygon::from(vec![(0., 0.), (4., 2.), (0., 4.), (2., 2.), (0., 0.)]);
+        let (position, _) = find_interior_position(&polygon).unwrap();
+        assert!(position.y != 2.);
+        assert!(position.x > 0.);
+    }
+
+    #[test]
+    fn check_multipolygon_uses_widest() {
+        let mp = MultiPolygon::from(vec![
+            vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)],
+            vec![(2., 0.), (6., 0.), (6., 1.), (2., 1.), (2., 0.)],
+        ]);
+        assert_eq!(mp.point_on_surface(), Some(Point::from((4., 0.5))));
+    }
+}
@@ -105,11 +105,13 @@ pub fn find_loop_loop_relation<C: Coordinate>(
         }
     }
 
-    let loop_1_relation = _get_loop_relation(&winding_numbers_1, &boundary_positions);
+    let loop_1_relation = _get_loop_relation(&winding_numbers_1, &boundary_positions)
+        .unwrap_or_else(|| _get_midpoint_relation(&segments_1, &segments_2));
     if loop_1_relation == LoopRelation::Crosses {
         return LoopLoopRelation::Crosses;
     }
-    let loop_2_relation = _get_loop_relation(&winding_numbers_2, &boundary_positions);
+    let loop_2_relation = _get_loop_relation(&winding_numbers_2, &boundary_positions)
+        .unwrap_or_else(|| _get_midpoint_relation(&segments_2, &segments_1));
     if loop_2_relation == LoopRelation::Crosses {
         return LoopLoopRelation::Crosses;
     }
@@ -154,10 +156,15 @@ fn _maybe_push_other_isxn<C: Coordinate>(
     }
 }
 
+/**
+ * Find the relation from the winding numbers of the positions that aren't on
+ * the other loop.  If every position is on the other loop, this can't tell,
+ * and returns None.
+ */
 fn _get_loop_relation<C: Coordinate>(
     winding_numbers: &HashMap<SafePosition<C>, i32>,
     boundary_positions: &HashSet<SafePosition<C>>,
-) -> LoopRelation {
+) -> Option<LoopRelation> {
     let mut outside = false;
     let mut inside = false;
     for pos in winding_numbers.keys() {
@@ -169,16 +176,47 @@ fn _get_loop_relation<C: Coordinate>(
         outside = outside || is_even;
         inside = inside || !is_even;
         if outside && inside {
-            return LoopRelation::Crosses;
+            return Some(LoopRelation::Crosses);
         }
     }
     if outside {
-        LoopRelation::Outside
+        Some(LoopRelation::Outside)
+    } else if inside {
+        Some(LoopRelation::Inside)
     } else {
-        LoopRelation::Inside
+        None
     }
 }
 
+/**
+ * Find the relation of a loop whose positions are all on the other loop, from
+ * the midpoints of its segments instead.  Since the loops don't cross, the
+ * first midpoint that isn't on the other loop decides.  This checks against
+ * every segment of the other loop, but is only needed in this rare case.
+ */
+fn _get_midpoint_relation<C: Coordinate>(
+    segments: &[Segment<C>],
+    other_segments: &[Segment<C>],
+) -> LoopRelation {
+    let two = C::one() + C::one();
+    for segment in segments {
+        let midpoint = (segment.start + segment.end) / two;
+        if other_segments.iter().any(|other| other.contains(midpoint)) {
+            continue;
+        }
+        let wn: i32 = other_segments
+            .iter()
+            .map(|&other| Segment::find_winding_number(midpoint, other))
+            .sum();
+        return if wn % 2 == 0 {
+            LoopRelation::Outside
+        } else {
+            LoopRelation::Inside
+        };
+    }
+    LoopRelation::Outside
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,6 +231,31 @@ mod tests {
         )
     }
 
+    #[test]
+    fn check_loop_all_positions_on_other() {
+        // The triangle is in the notch, touching the notched square only at
+        // its positions.
+        let notched = LineString::from(vec![
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 4.0),
+            (3.0, 2.0),
+            (2.0, 1.0),
+            (1.0, 2.0),
+            (0.0, 4.0),
+            (0.0, 0.0),
+        ]);
+        let triangle = LineString::from(vec![(4.0, 4.0), (0.0, 4.0), (2.0, 1.0), (4.0, 4.0)]);
+        assert_eq!(
+            find_loop_loop_relation(&notched, &triangle),
+            LoopLoopRelation::Separate
+        );
+        assert_eq!(
+            find_loop_loop_relation(&triangle, &notched),
+            LoopLoopRelation::Separate
+        );
+    }
+
     #[test]
     fn check_loop_equal_crossing() {
         let loop_a = LineString::from(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (0.0, 0.0)]);
@@ -0,0 +1,799 @@
+use super::snap_rounding::snap_round_segments;
+use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
+use crate::planar::primitives::{
+    orient2d, Envelope, Position, PrecisionModel, SafePosition, Segment,
+};
+use crate::planar::properties::area::get_signed_loop_area;
+use crate::planar::types::{
+    Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+use crate::Coordinate;
+use std::collections::{HashMap, HashSet};
+
+/// How to decide which areas enclosed by self-intersecting rings are inside.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// A position is inside if a ray from it crosses the rings an odd number of times.
+    EvenOdd,
+    /**
+     * A position is inside if the rings wind around it a non-zero number of
+     * times.  Exteriors are first oriented counter-clockwise and interiors
+     * clockwise, so holes are subtracted.
+     */
+    NonZero,
+}
+
+/// A noded edge, with the number of times the rings cross it from right to left.
+type WeightedEdge<C> = (Segment<C>, i32);
+
+/**
+ * Make a valid geometry covering the area of the polygons.
+ *
+ * Positions with non-finite coordinates and repeated positions are removed,
+ * and unclosed rings are closed.  Then all the rings are noded together, and
+ * each area they enclose is kept if it is inside by the fill rule.  The
+ * boundary of the kept area is traced into simple rings: counter-clockwise
+ * exteriors and clockwise interiors, each interior in the smallest exterior
+ * around it.  So self-intersecting rings are split, and holes outside their
+ * exterior become polygons of their own.
+ *
+ * If nothing is inside, the polygons have collapsed, and the linework of
+ * their rings is returned instead (see `make_valid_lines`).
+ */
+pub fn make_valid_polygons<C: Coordinate>(polygons: &[Polygon<C>], rule: FillRule) -> Geometry<C> {
+    make_valid_polygons_with_precision(polygons, rule, &PrecisionModel::Floating)
+}
+
+/**
+ * Make a valid geometry covering the area of the polygons, as
+ * `make_valid_polygons` does, with every position, including computed
+ * crossings, rounded to a precision model.  The rings are noded by snap
+ * rounding (see `snap_round_segments`); with a floating model, to a grid
+ * finer than any meaningful difference between the coordinates.
+ */
+pub fn make_valid_polygons_with_precision<C: Coordinate>(
+    polygons: &[Polygon<C>],
+    rule: FillRule,
+    precision_model: &PrecisionModel<C>,
+) -> Geometry<C> {
+    let mut segments = Vec::new();
+    for polygon in polygons {
+        let rings = std::iter::once(&polygon.exterior).chain(polygon.interiors.iter());
+        for (index, ring) in rings.enumerate() {
+            let mut positions = _clean_positions(&ring.positions, precision_model);
+            if positions.len() > 1 && positions.first() != positions.last() {
+                positions.push(positions[0]);
+            }
+            let mut cleaned = LineString::new(positions);
+            if rule == FillRule::NonZero
+                && (get_signed_loop_area(&cleaned) > C::zero()) != (index == 0)
+            {
+                cleaned.positions.reverse();
+            }
+            segments.extend(cleaned.segments_iter());
+        }
+    }
+    let pieces = snap_round_segments(&segments, &_noding_model(&segments, precision_model));
+
+    let edges = _find_boundary_edges(&_weigh_edges(&pieces), rule);
+    let mut shells = Vec::new();
+    let mut holes = Vec::new();
+    for ring in _trace_rings(&edges) {
+        let area = get_signed_loop_area(&ring);
+        if area > C::zero() {
+            shells.push((area, ring));
+        } else if area < C::zero() {
+            holes.push(ring);
+        }
+    }
+    if edges.is_empty() {
+        let positions: Vec<Position<C>> = polygons
+            .iter()
+            .flat_map(|p| p.exterior.positions.iter().copied())
+            .collect();
+        return _collapse(pieces, &positions);
+    }
+
+    let mut interiors: Vec<Vec<LineString<C>>> = vec![Vec::new(); shells.len()];
+    for hole in holes {
+        // Noded boundary edges only meet at their ends, so the middle of an
+        // interior's edge is strictly inside or outside the other rings.
+        let middle = (hole.positions[0] + hole.positions[1]) / (C::one() + C::one());
+        // The smallest exterior around an interior is the one it belongs to.
+        let shell = shells
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, shell))| _ring_winding_number(&shell.positions, middle) != 0)
+            .min_by(|(_, (a, _)), (_, (b, _))| a.partial_cmp(b).unwrap())
+            .map(|(index, _)| index);
+        if let Some(shell) = shell {
+            interiors[shell].push(hole);
+        }
+    }
+    let mut result: Vec<Polygon<C>> = shells
+        .into_iter()
+        .zip(interiors)
+        .map(|((_, shell), interiors)| Polygon::new(shell, interiors))
+        .collect();
+    if result.len() == 1 {
+        Geometry::from(result.pop().unwrap())
+    } else {
+        Geometry::from(MultiPolygon::new(result))
+    }
+}
+
+/**
+ * Make a valid geometry covering the linestrings.
+ *
+ * Positions with non-finite coordinates and repeated positions are removed,
+ * and the linestrings are noded together.  Overlapping parts are kept once,
+ * and the pieces are merged into linestrings at positions where exactly two
+ * pieces meet.  So the result only meets itself at endpoints.  Linestrings
+ * that collapse to a position become a Point.
+ */
+pub fn make_valid_lines<C: Coordinate>(linestrings: &[LineString<C>]) -> Geometry<C> {
+    make_valid_lines_with_precision(linestrings, &PrecisionModel::Floating)
+}
+
+/**
+ * Make a valid geometry covering the linestrings, as `make_valid_lines`
+ * does, with every position rounded to a precision model.  The lines are
+ * noded by snap rounding, as in `make_valid_polygons_with_precision`.
+ */
+pub fn make_valid_lines_with_precision<C: Coordinate>(
+    linestrings: &[LineString<C>],
+    precision_model: &PrecisionModel<C>,
+) -> Geometry<C> {
+    let mut segments = Vec::new();
+    let mut positions = Vec::new();
+    for linestring in linestrings {
+        let cleaned = _clean_positions(&linestring.positions, precision_model);
+        segments.extend(cleaned.windows(2).map(|p| Segment::new(p[0], p[1])));
+        positions.extend(cleaned);
+    }
+    let model = _noding_model(&segments, precision_model);
+    _collapse(snap_round_segments(&segments, &model), &positions)
+}
+
+/**
+ * The model to node segments with.
+ *
+ * A crossing computed from different pairs of segments can differ in its
+ * last bits, which leaves gaps in the noded linework.  So segments with a
+ * floating model are snap rounded to a fine grid instead: a power of two, so
+ * that small integers and halves are kept exactly, and about a thousand
+ * times the rounding error of the largest coordinate.
+ */
+fn _noding_model<C: Coordinate>(
+    segments: &[Segment<C>],
+    precision_model: &PrecisionModel<C>,
+) -> PrecisionModel<C> {
+    if !precision_model.is_floating() {
+        return *precision_model;
+    }
+    let max = segments
+        .iter()
+        .flat_map(|s| [s.start, s.end])
+        .fold(C::zero(), |max, p| max.max(p.x.abs()).max(p.y.abs()));
+    if max == C::zero() {
+        return *precision_model;
+    }
+    let epsilon = match precision_model {
+        PrecisionModel::FloatingSingle => C::from(f32::EPSILON).unwrap(),
+        _ => C::epsilon(),
+    };
+    let two = C::one() + C::one();
+    let bits = (-epsilon.log2()).round() - C::from(10).unwrap() - max.log2().ceil();
+    PrecisionModel::fixed(two.powi(bits.to_i32().unwrap())).unwrap_or(*precision_model)
+}
+
+/// Return the merged lines of the noded pieces, or a Point if there are none.
+fn _collapse<C: Coordinate>(pieces: Vec<Segment<C>>, positions: &[Position<C>]) -> Geometry<C> {
+    let mut lines = _merge_lines(pieces);
+    if lines.len() > 1 {
+        return Geometry::from(MultiLineString::new(lines));
+    } else if let Some(line) = lines.pop() {
+        return Geometry::from(line);
+    }
+    match positions.iter().find(|p| p.validate().is_ok()) {
+        Some(&position) => Geometry::from(Point(position)),
+        None => Geometry::empty(),
+    }
+}
+
+/**
+ * Remove positions with non-finite coordinates, round the rest to the
+ * precision model, and then remove repeated positions.
+ */
+fn _clean_positions<C: Coordinate>(
+    positions: &[Position<C>],
+    precision_model: &PrecisionModel<C>,
+) -> Vec<Position<C>> {
+    let mut cleaned: Vec<Position<C>> = positions
+        .iter()
+        .filter(|p| p.validate().is_ok())
+        .map(|&p| precision_model.make_precise_position(p))
+        .collect();
+    cleaned.dedup();
+    cleaned
+}
+
+fn _edge_key<C: Coordinate>(segment: Segment<C>) -> (SafePosition<C>, SafePosition<C>) {
+    (
+        segment.start.to_hashable().unwrap(),
+        segment.end.to_hashable().unwrap(),
+    )
+}
+
+/**
+ * Combine the noded pieces into distinct edges, each with the number of times
+ * the rings traverse it in its direction, less the number in reverse, and the
+ * total number of times.
+ */
+fn _weigh_edges<C: Coordinate>(pieces: &[Segment<C>]) -> Vec<(Segment<C>, i32, u32)> {
+    let mut indices: HashMap<(SafePosition<C>, SafePosition<C>), usize> = HashMap::new();
+    let mut edges: Vec<(Segment<C>, i32, u32)> = Vec::new();
+    for &piece in pieces {
+        let reversed = Segment::new(piece.end, piece.start);
+        if let Some(&index) = indices.get(&_edge_key(piece)) {
+            edges[index].1 += 1;
+            edges[index].2 += 1;
+        } else if let Some(&index) = indices.get(&_edge_key(reversed)) {
+            edges[index].1 -= 1;
+            edges[index].2 += 1;
+        } else {
+            indices.insert(_edge_key(piece), edges.len());
+            edges.push((piece, 1, 1));
+        }
+    }
+    edges
+}
+
+/**
+ * Find the edges between inside and outside, directed with the inside on
+ * their left.
+ *
+ * Edges that don't change the winding number (or parity) can't separate
+ * inside from outside, so they are dropped first.  The winding number on one
+ * side of each remaining edge is found by casting a ray from its middle, and
+ * the other side differs by the edge's weight.
+ */
+fn _find_boundary_edges<C: Coordinate>(
+    edges: &[(Segment<C>, i32, u32)],
+    rule: FillRule,
+) -> Vec<Segment<C>> {
+    let weighted: Vec<WeightedEdge<C>> = edges
+        .iter()
+        .filter_map(|&(segment, winding, count)| match rule {
+            FillRule::EvenOdd if count % 2 == 1 => Some((segment, 1)),
+            FillRule::NonZero if winding != 0 => Some((segment, winding)),
+            _ => None,
+        })
+        .collect();
+    let is_inside = |winding: i32| match rule {
+        FillRule::EvenOdd => winding % 2 != 0,
+        FillRule::NonZero => winding != 0,
+    };
+
+    let segments: Vec<Segment<C>> = weighted.iter().map(|&(s, _)| s).collect();
+    let rtree = Flatbush::new(&segments, FLATBUSH_DEFAULT_DEGREE);
+    let max = match Envelope::of(segments.iter()) {
+        Envelope::Bounds { max, .. } => max,
+        Envelope::Empty => return Vec::new(),
+    };
+
+    let mut boundary = Vec::new();
+    for (index, &(segment, weight)) in weighted.iter().enumerate() {
+        let (start, end) = (segment.start, segment.end);
+        let middle = (start + end) / (C::one() + C::one());
+        let left = if start.y == end.y {
+            let ray = Envelope::new(middle, Position::new(middle.x, max.y));
+            let north = _ray_winding_number(&weighted, &rtree, ray, index, middle, true);
+            if start.x < end.x {
+                north
+            } else {
+                north + weight
+            }
+        } else {
+            let ray = Envelope::new(middle, Position::new(max.x, middle.y));
+            let east = _ray_winding_number(&weighted, &rtree, ray, index, middle, false);
+            if start.y < end.y {
+                east + weight
+            } else {
+                east
+            }
+        };
+        let right = left - weight;
+        if is_inside(left) && !is_inside(right) {
+            boundary.push(segment);
+        } else if is_inside(right) && !is_inside(left) {
+            boundary.push(Segment::new(end, start));
+        }
+    }
+    boundary
+}
+
+/**
+ * The winding number just beside `position`, which is on edge `skip`, from
+ * the other edges crossing a ray east (or north) from it.  Crossings at
+ * vertices are counted on half-open intervals, so they count once.
+ */
+fn _ray_winding_number<C: Coordinate>(
+    edges: &[WeightedEdge<C>],
+    rtree: &Flatbush<C>,
+    ray: Envelope<C>,
+    skip: usize,
+    position: Position<C>,
+    north: bool,
+) -> i32 {
+    let mut winding = 0;
+    for index in rtree.find_intersection_candidates(ray) {
+        if index == skip {
+            continue;
+        }
+        let (segment, weight) = edges[index];
+        let (a, b) = (segment.start, segment.end);
+        let side = orient2d(a, b, position);
+        let (forward, backward) = if north {
+            // Westward edges to the north go counter-clockwise around position.
+            (
+                b.x <= position.x && position.x < a.x,
+                a.x <= position.x && position.x < b.x,
+            )
+        } else {
+            (
+                a.y <= position.y && position.y < b.y,
+                b.y <= position.y && position.y < a.y,
+            )
+        };
+        if forward && side > C::zero() {
+            winding += weight;
+        } else if backward && side < C::zero() {
+            winding -= weight;
+        }
+    }
+    winding
+}
+
+/// The winding number of a closed ring around a position not on it.
+fn _ring_winding_number<C: Coordinate>(ring: &[Position<C>], position: Position<C>) -> i32 {
+    let mut winding = 0;
+    for pair in ring.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let side = orient2d(a, b, position);
+        if a.y <= position.y && position.y < b.y && side > C::zero() {
+            winding += 1;
+        } else if b.y <= position.y && position.y < a.y && side < C::zero() {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/**
+ * Trace directed boundary edges into simple closed rings.
+ *
+ * At each position, the walk takes the sharpest left turn, which keeps it
+ * around a single piece of inside.  Walks that still visit a position twice
+ * are split there into simple rings.
+ */
+fn _trace_rings<C: Coordinate>(edges: &[Segment<C>]) -> Vec<LineString<C>> {
+    let mut outgoing: HashMap<SafePosition<C>, Vec<usize>> = HashMap::new();
+    for (index, edge) in edges.iter().enumerate() {
+        outgoing
+            .entry(edge.start.to_hashable().unwrap())
+            .or_default()
+            .push(index);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut walk = vec![edges[first].start];
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let edge = edges[current];
+            walk.push(edge.end);
+            if edge.end == walk[0] {
+                break;
+            }
+            let back = edge.start - edge.end;
+            let next = outgoing
+                .get(&edge.end.to_hashable().unwrap())
+                .and_then(|candidates| {
+                    candidates
+                        .iter()
+                        .copied()
+                        .filter(|&i| !used[i])
+                        .min_by(|&i, &j| {
+                            let (a, b) = (
+                                _clockwise_angle(back, edges[i].end - edges[i].start),
+                                _clockwise_angle(back, edges[j].end - edges[j].start),
+                            );
+                            a.partial_cmp(&b).unwrap()
+                        })
+                });
+            match next {
+                Some(next) => current = next,
+                // Only possible if the edges aren't balanced, from rounding.
+                None => break,
+            }
+        }
+        if walk.first() == walk.last() {
+            rings.extend(LineString::new(walk)._split_loops());
+        }
+    }
+    rings
+}
+
+/// The angle turning clockwise from one direction to another, in (0, 2 pi].
+fn _clockwise_angle<C: Coordinate>(from: Position<C>, to: Position<C>) -> C {
+    let two_pi = C::from(std::f64::consts::PI * 2.).unwrap();
+    let angle = Position::cross(to, from).atan2(Position::dot(from, to));
+    if angle <= C::zero() {
+        angle + two_pi
+    } else {
+        angle
+    }
+}
+
+/**
+ * Merge noded pieces into linestrings, joining them at positions where
+ * exactly two distinct pieces meet.
+ *
+ * A merged linestring that starts and ends at the same node is split in the
+ * middle, so that every linestring meeting another does so at its endpoints.
+ */
+fn _merge_lines<C: Coordinate>(pieces: Vec<Segment<C>>) -> Vec<LineString<C>> {
+    let mut seen = HashSet::new();
+    let mut edges = Vec::new();
+    for piece in pieces {
+        let (a, b) = (
+            piece.start.to_hashable().unwrap(),
+            piece.end.to_hashable().unwrap(),
+        );
+        if seen.insert((a.min(b), a.max(b))) {
+            edges.push(piece);
+        }
+    }
+
+    let mut incident: HashMap<SafePosition<C>, Vec<usize>> = HashMap::new();
+    for (index, edge) in edges.iter().enumerate() {
+        for position in &[edge.start, edge.end] {
+            incident
+                .entry(position.to_hashable().unwrap())
+                .or_default()
+                .push(index);
+        }
+    }
+    let is_node = |p: Position<C>| incident[&p.to_hashable().unwrap()].len() != 2;
+
+    let mut used = vec![false; edges.len()];
+    let mut lines = Vec::new();
+    // Start at nodes first, so only closed loops are left for the second pass.
+    for from_nodes in &[true, false] {
+        for first in 0..edges.len() {
+            if used[first] {
+                continue;
+            }
+            let edge = edges[first];
+            let start = if is_node(edge.start) || !from_nodes {
+                edge.start
+            } else if is_node(edge.end) {
+                edge.end
+            } else {
+                continue;
+            };
+            let mut line = vec![start];
+            let mut current = first;
+            loop {
+                used[current] = true;
+                let edge = edges[current];
+                let last = *line.last().unwrap();
+                let position = if edge.start == last {
+                    edge.end
+                } else {
+                    edge.start
+                };
+                line.push(position);
+                if is_node(position) || position == line[0] {
+                    break;
+                }
+                match incident[&position.to_hashable().unwrap()]
+                    .iter()
+                    .find(|&&i| !used[i])
+                {
+                    Some(&next) => current = next,
+                    None => break,
+                }
+            }
+            if *from_nodes && line.len() > 2 && line.first() == line.last() {
+                let middle = line.len() / 2;
+                lines.push(LineString::new(line[..=middle].to_vec()));
+                lines.push(LineString::new(line[middle..].to_vec()));
+            } else {
+                lines.push(LineString::new(line));
+            }
+        }
+    }
+    lines
+}
+
+/// Remove non-finite and duplicate points.
+pub(crate) fn make_valid_points<C: Coordinate>(points: &[Point<C>]) -> MultiPoint<C> {
+    let mut seen = HashSet::new();
+    MultiPoint::new(
+        points
+            .iter()
+            .filter(|p| p.validate().is_ok() && seen.insert(p.0.to_hashable().unwrap()))
+            .cloned()
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::algorithms::random::Lcg;
+    use crate::planar::properties::Area;
+
+    fn area(geometry: &Geometry<f64>) -> f64 {
+        match geometry {
+            Geometry::Polygon(p) => p.area(),
+            Geometry::MultiPolygon(mp) => mp.area(),
+            _ => 0.,
+        }
+    }
+
+    #[test]
+    fn check_bowtie() {
+        let bowtie = Polygon::from(vec![(0., 0.), (2., 2.), (2., 0.), (0., 2.), (0., 0.)]);
+        assert!(bowtie.validate().is_err());
+        for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+            let valid = bowtie.make_valid(rule);
+            assert!(valid.validate().is_ok());
+            let mp = valid.clone().as_multipolygon().unwrap();
+            assert_eq!(mp.polygons.len(), 2);
+            assert_eq!(area(&Geometry::from(mp)), 2.);
+        }
+    }
+
+    #[test]
+    fn check_repeated_and_unclosed() {
+        let polygon = Polygon::from(vec![(0., 0.), (1., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        let valid = polygon.make_valid(FillRule::EvenOdd);
+        assert!(valid.validate().is_ok());
+        assert_eq!(area(&valid), 1.);
+        assert_eq!(valid.as_polygon().unwrap().exterior.num_points(), 5);
+    }
+
+    #[test]
+    fn check_escaped_hole() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(0.5, 0.5), (1., 0.5), (1., 1.), (0.5, 0.5)]),
+                LineString::from(vec![(5., 5.), (6., 5.), (6., 6.), (5., 5.)]),
+            ],
+        );
+        assert!(polygon.validate().is_err());
+        for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+            let valid = polygon.make_valid(rule);
+            assert!(valid.validate().is_ok());
+            let mp = valid.clone().as_multipolygon().unwrap();
+            assert_eq!(mp.polygons.len(), 2);
+            assert_eq!(
+                mp.polygons.iter().map(|p| p.interiors.len()).sum::<usize>(),
+                1
+            );
+            assert_eq!(area(&Geometry::from(mp)), 4. - 0.125 + 0.5);
+        }
+    }
+
+    #[test]
+    fn check_fill_rules_differ() {
+        // Two overlapping squares, both counter-clockwise.
+        let mp = MultiPolygon::from(vec![
+            vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)],
+            vec![(1., 1.), (3., 1.), (3., 3.), (1., 3.), (1., 1.)],
+        ]);
+        let union = mp.make_valid(FillRule::NonZero);
+        assert!(union.validate().is_ok());
+        assert_eq!(area(&union), 7.);
+        let xor = mp.make_valid(FillRule::EvenOdd);
+        assert!(xor.validate().is_ok());
+        assert_eq!(area(&xor), 6.);
+    }
+
+    #[test]
+    fn check_self_touching_hole() {
+        // The exterior pinches in to touch itself, enclosing a hole.
+        let polygon = Polygon::from(vec![
+            (0., 0.),
+            (4., 0.),
+            (4., 4.),
+            (2., 4.),
+            (3., 2.),
+            (1., 2.),
+            (2., 4.),
+            (0., 4.),
+            (0., 0.),
+        ]);
+        assert!(polygon.validate().is_err());
+        let valid = polygon.make_valid(FillRule::EvenOdd);
+        assert!(valid.validate().is_ok());
+        let valid = valid.as_polygon().unwrap();
+        assert_eq!(valid.interiors.len(), 1);
+        assert_eq!(area(&Geometry::from(valid)), 14.);
+    }
+
+    #[test]
+    fn check_collapsed() {
+        let polygon = Polygon::from(vec![(0., 0.), (1., 1.), (2., 2.), (0., 0.)]);
+        assert_eq!(
+            polygon.make_valid(FillRule::EvenOdd),
+            Geometry::from(LineString::from(vec![(0., 0.), (1., 1.), (2., 2.)]))
+        );
+        let polygon = Polygon::from(vec![(1., 1.), (1., 1.), (1., 1.), (1., 1.)]);
+        assert_eq!(
+            polygon.make_valid(FillRule::EvenOdd),
+            Geometry::from(Point::from((1., 1.)))
+        );
+    }
+
+    #[test]
+    fn check_lines() {
+        let ls = LineString::from(vec![(0., 0.), (2., 2.), (2., 0.), (0., 2.)]);
+        let valid = ls.make_valid();
+        assert!(valid.validate().is_ok());
+        // The loop through the crossing is split in two.
+        assert_eq!(valid.as_multilinestring().unwrap().line_strings.len(), 4);
+
+        let loop_ls = LineString::from(vec![(0., 0.), (2., 0.), (1., 1.), (1., -1.)]);
+        let valid = loop_ls.make_valid();
+        assert!(valid.validate().is_ok());
+
+        let ls = LineString::from(vec![(0., 0.), (1., 0.), (1., 0.), (f64::NAN, 0.), (2., 0.)]);
+        assert_eq!(
+            ls.make_valid(),
+            Geometry::from(LineString::from(vec![(0., 0.), (1., 0.), (2., 0.)]))
+        );
+    }
+
+    #[test]
+    fn check_points() {
+        let mp = MultiPoint::from(vec![(0., 0.), (1., f64::INFINITY), (0., 0.), (2., 1.)]);
+        assert_eq!(
+            mp.make_valid(),
+            Geometry::from(MultiPoint::from(vec![(0., 0.), (2., 1.)]))
+        );
+    }
+
+    #[test]
+    fn check_random_rings() {
+        // Random rings cross themselves many times; the result must be valid.
+        let mut rng = Lcg::new(12345);
+        let mut next = || rng.next_below(1000) as f64 / 100.;
+        for _ in 0..50 {
+            let mut ring: Vec<(f64, f64)> = (0..12).map(|_| (next(), next())).collect();
+            ring.push(ring[0]);
+            let polygon = Polygon::from(ring);
+            for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+                let valid = polygon.make_valid(rule);
+                assert!(valid.validate().is_ok(), "{:?}", valid.validate());
+                assert!(area(&valid) > 0.);
+                // Snap rounded onto a coarse grid, they must still be valid.
+                let model = PrecisionModel::fixed(4.).unwrap();
+                let geometry = Geometry::from(polygon.clone());
+                let valid = geometry.make_valid_with_precision(rule, &model);
+                assert!(valid.validate().is_ok(), "{:?}", valid.validate());
+            }
+        }
+    }
+
+    #[test]
+    fn check_crossings_computed_twice() {
+        // Crossings found from different pairs of segments round differently,
+        // which used to leave the rings unclosed, and return linework.
+        let cases = vec![
+            (
+                vec![
+                    (3., 3.),
+                    (5., 3.),
+                    (4., 2.),
+                    (0., 0.),
+                    (0., 5.),
+                    (4., 1.),
+                    (2., 3.),
+                    (2., 2.),
+                    (4., 3.),
+                    (3., 3.),
+                ],
+                FillRule::EvenOdd,
+            ),
+            (
+                vec![
+                    (4., 2.),
+                    (0., 2.),
+                    (1., 5.),
+                    (1., 4.),
+                    (3., 5.),
+                    (2., 3.),
+                    (3., 1.),
+                    (1., 3.),
+                    (3., 4.),
+                    (1., 3.),
+                    (4., 2.),
+                ],
+                FillRule::NonZero,
+            ),
+        ];
+        for (ring, rule) in cases {
+            let valid = Polygon::from(ring).make_valid(rule);
+            assert!(valid.validate().is_ok(), "{:?}", valid.validate());
+            assert!(area(&valid) > 0.);
+        }
+    }
+
+    #[test]
+    fn check_random_grid_rings() {
+        // Rings on a small integer grid have many crossings that are computed
+        // more than once, and must still be noded consistently.
+        let mut rng = Lcg::new(777);
+        for _ in 0..3000 {
+            let n = 4 + rng.next_below(7);
+            let mut ring: Vec<(f64, f64)> = (0..n)
+                .map(|_| (rng.next_below(6) as f64, rng.next_below(6) as f64))
+                .collect();
+            ring.push(ring[0]);
+            let polygon = Polygon::from(ring);
+            for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+                let valid = polygon.make_valid(rule);
+                assert!(
+                    valid.validate().is_ok(),
+                    "{:?} {:?}: {:?}",
+                    polygon,
+                    rule,
+                    valid.validate()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn check_make_valid_with_precision() {
+        // A bowtie, whose rings cross at (1/3, 1/3).
+        let bowtie = Geometry::from(Polygon::from(vec![
+            (0., 0.),
+            (1., 1.),
+            (1., 0.),
+            (0., 0.5),
+            (0., 0.),
+        ]));
+        let model = PrecisionModel::fixed(10.).unwrap();
+        let valid = bowtie.make_valid_with_precision(FillRule::EvenOdd, &model);
+        assert!(valid.validate().is_ok());
+        assert!(valid
+            .coords_iter()
+            .all(|p| model.make_precise_position(p) == p));
+        assert!(valid.coords_iter().any(|p| p == Position::new(0.3, 0.3)));
+
+        // Valid geometries are only snapped.
+        let square = Geometry::from(Polygon::from(vec![
+            (0.01, 0.),
+            (1., 0.),
+            (1., 1.),
+            (0., 1.),
+            (0.01, 0.),
+        ]));
+        assert_eq!(
+            square.make_valid_with_precision(FillRule::EvenOdd, &model),
+            square.snap_to_grid(&model)
+        );
+    }
+}
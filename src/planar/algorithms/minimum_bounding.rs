@@ -0,0 +1,264 @@
+use super::convex_hull::find_convex_hull;
+use super::random::shuffle;
+use crate::planar::primitives::{Position, Segment};
+use crate::planar::types::{Geometry, LineString, Polygon};
+use crate::Coordinate;
+
+/**
+ * Find the smallest circle containing all the positions, as its center and
+ * radius.
+ *
+ * This is Welzl's algorithm, in its iterative form: the positions are
+ * visited in a shuffled (but deterministic) order, and whenever one is
+ * outside the current circle, it must be on the boundary of the new circle,
+ * which is found from it and the previous positions.  The expected time is
+ * linear.
+ *
+ * Positions with NaN coordinates are ignored.  If there are no others,
+ * return None.
+ */
+pub fn find_minimum_bounding_circle<C: Coordinate>(
+    positions: impl IntoIterator<Item = Position<C>>,
+) -> Option<(Position<C>, C)> {
+    let mut positions: Vec<Position<C>> = positions
+        .into_iter()
+        .filter(|p| !p.x.is_nan() && !p.y.is_nan())
+        .collect();
+    shuffle(&mut positions);
+
+    let mut circle = (*positions.first()?, C::zero());
+    for i in 1..positions.len() {
+        if _is_in_circle(circle, positions[i]) {
+            continue;
+        }
+        circle = (positions[i], C::zero());
+        for j in 0..i {
+            if _is_in_circle(circle, positions[j]) {
+                continue;
+            }
+            circle = _circle_of_two(positions[i], positions[j]);
+            for k in 0..j {
+                if !_is_in_circle(circle, positions[k]) {
+                    circle = _circle_of_three(positions[i], positions[j], positions[k]);
+                }
+            }
+        }
+    }
+    Some(circle)
+}
+
+fn _is_in_circle<C: Coordinate>(circle: (Position<C>, C), position: Position<C>) -> bool {
+    let (center, radius) = circle;
+    let tolerance =
+        C::epsilon() * C::from(16).unwrap() * (radius + center.x.abs() + center.y.abs());
+    Segment::new(center, position).length() <= radius + tolerance
+}
+
+fn _circle_of_two<C: Coordinate>(a: Position<C>, b: Position<C>) -> (Position<C>, C) {
+    let center = (a + b) / (C::one() + C::one());
+    (center, Segment::new(center, a).length())
+}
+
+/// The circumcircle, or if the positions are collinear, the circle of the farthest pair.
+fn _circle_of_three<C: Coordinate>(
+    a: Position<C>,
+    b: Position<C>,
+    c: Position<C>,
+) -> (Position<C>, C) {
+    let (d, e) = (b - a, c - a);
+    let denominator = (C::one() + C::one()) * Position::cross(d, e);
+    if denominator == C::zero() {
+        let pairs = [(a, b), (b, c), (a, c)];
+        let &(p, q) = pairs
+            .iter()
+            .max_by(|x, y| {
+                let (lx, ly) = (
+                    Segment::new(x.0, x.1).length(),
+                    Segment::new(y.0, y.1).length(),
+                );
+                lx.partial_cmp(&ly).unwrap()
+            })
+            .unwrap();
+        return _circle_of_two(p, q);
+    }
+    let (dl, el) = (Position::dot(d, d), Position::dot(e, e));
+    let offset = Position::new(
+        (e.y * dl - d.y * el) / denominator,
+        (d.x * el - e.x * dl) / denominator,
+    );
+    (
+        a + offset,
+        Segment::new(Position::new(C::zero(), C::zero()), offset).length(),
+    )
+}
+
+/**
+ * Find the rectangle of least area containing all the positions.
+ *
+ * One side of the minimum rectangle lies along an edge of the convex hull.
+ * This uses rotating calipers: for each hull edge in turn, the hull
+ * positions farthest along the edge, behind it, and away from it are found
+ * by advancing them from their positions for the previous edge.
+ *
+ * The rectangle is returned as a Polygon with a counter-clockwise exterior.
+ * If the positions are collinear (or all the same), it has zero area.  If
+ * there are no positions (except with NaN coordinates), return None.
+ */
+pub fn find_minimum_rotated_rectangle<C: Coordinate>(
+    positions: impl IntoIterator<Item = Position<C>>,
+) -> Option<Polygon<C>> {
+    let hull: Vec<Position<C>> = match find_convex_hull(positions) {
+        Geometry::Point(p) => vec![p.0],
+        Geometry::LineString(ls) => ls.positions,
+        Geometry::Polygon(p) => {
+            let mut positions = p.exterior.positions;
+            positions.pop();
+            positions
+        }
+        _ => return None,
+    };
+    if hull.len() < 3 {
+        // The rectangle is degenerate: the hull is a point or a segment.
+        let (a, b) = (hull[0], hull[hull.len() - 1]);
+        return Some(Polygon::from(LineString::new(vec![a, b, b, a, a])));
+    }
+
+    let n = hull.len();
+    let next = |i: usize| (i + 1) % n;
+    let mut best: Option<(C, [Position<C>; 4])> = None;
+    let (mut right, mut top, mut left) = (0, 0, 0);
+    for i in 0..n {
+        let origin = hull[i];
+        let edge = hull[next(i)] - origin;
+        let u = edge / Segment::new(hull[i], hull[next(i)]).length();
+        let v = Position::new(-u.y, u.x);
+        let along = |k: usize| Position::dot(hull[k] - origin, u);
+        let away = |k: usize| Position::dot(hull[k] - origin, v);
+
+        if i == 0 {
+            right = (0..n).max_by(|&a, &b| along(a).partial_cmp(&along(b)).unwrap())?;
+            top = (0..n).max_by(|&a, &b| away(a).partial_cmp(&away(b)).unwrap())?;
+            left = (0..n).min_by(|&a, &b| along(a).partial_cmp(&along(b)).unwrap())?;
+        }
+        // The calipers only ever rotate forward.
+        for _ in 0..n {
+            if along(next(right)) < along(right) {
+                break;
+            }
+            right = next(right);
+        }
+        for _ in 0..n {
+            if away(next(top)) < away(top) {
+                break;
+            }
+            top = next(top);
+        }
+        for _ in 0..n {
+            if along(next(left)) > along(left) {
+                break;
+            }
+            left = next(left);
+        }
+
+        let (min_u, max_u, max_v) = (along(left), along(right), away(top));
+        let area = (max_u - min_u) * max_v;
+        let is_smaller = match best {
+            Some((best_area, _)) => area < best_area,
+            None => true,
+        };
+        if is_smaller {
+            let corners = [
+                origin + u * min_u,
+                origin + u * max_u,
+                origin + u * max_u + v * max_v,
+                origin + u * min_u + v * max_v,
+            ];
+            best = Some((area, corners));
+        }
+    }
+    let (_, corners) = best?;
+    let mut ring = corners.to_vec();
+    ring.push(corners[0]);
+    Some(Polygon::from(LineString::new(ring)))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::properties::Area;
+    use crate::planar::types::{MultiPoint, Point};
+
+    fn assert_near(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn check_circle_empty_and_point() {
+        let empty: MultiPoint<f64> = MultiPoint::new(Vec::new());
+        assert_eq!(empty.minimum_bounding_circle(), None);
+        let point = Point::from((1., 2.));
+        assert_eq!(point.minimum_bounding_circle(), Some((point, 0.)));
+    }
+
+    #[test]
+    fn check_circle_of_diameter() {
+        let mp = MultiPoint::from(vec![(0., 0.), (1., 0.2), (4., 0.), (2., -1.)]);
+        let (center, radius): (Point<f64>, f64) = mp.minimum_bounding_circle().unwrap();
+        assert_eq!(center, Point::from((2., 0.)));
+        assert_eq!(radius, 2.);
+    }
+
+    #[test]
+    fn check_circle_of_triangle() {
+        // An acute triangle: the circle is its circumcircle.
+        let mp = MultiPoint::from(vec![(0., 0.), (4., 0.), (2., 3.), (2., 1.)]);
+        let (center, radius): (Point<f64>, f64) = mp.minimum_bounding_circle().unwrap();
+        assert_near(center.x(), 2.);
+        assert_near(center.y(), 5. / 6.);
+        assert_near(radius, 13. / 6.);
+        for point in &mp.points {
+            assert!(Segment::new(center.0, point.0).length() <= radius + 1e-9);
+        }
+    }
+
+    #[test]
+    fn check_rectangle_of_rotated_square() {
+        let polygon = Polygon::from(vec![(1., 0.), (2., 1.), (1., 2.), (0., 1.), (1., 0.)]);
+        let rectangle = polygon.minimum_rotated_rectangle().unwrap();
+        let mut corners: Vec<Position<f64>> = rectangle.exterior.positions[..4].to_vec();
+        for corner in corners.iter_mut() {
+            *corner = Position::new(corner.x.round(), corner.y.round());
+        }
+        corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let expected: Vec<Position<f64>> = vec![(0., 1.), (1., 0.), (1., 2.), (2., 1.)]
+            .into_iter()
+            .map(Position::from)
+            .collect();
+        assert_eq!(corners, expected);
+    }
+
+    #[test]
+    fn check_rectangle_area() {
+        // A long thin diagonal shape: the rectangle is far smaller than the envelope.
+        let ls = LineString::from(vec![(0., 0.), (10., 10.), (10.5, 9.5), (0.5, -0.5)]);
+        let rectangle = ls.minimum_rotated_rectangle().unwrap();
+        assert_near(rectangle.area(), 200f64.sqrt() * 0.5f64.sqrt());
+    }
+
+    #[test]
+    fn check_rectangle_degenerate() {
+        let ls = LineString::from(vec![(0., 0.), (1., 1.), (2., 2.)]);
+        assert_eq!(
+            ls.minimum_rotated_rectangle(),
+            Some(Polygon::from(vec![
+                (0., 0.),
+                (2., 2.),
+                (2., 2.),
+                (0., 0.),
+                (0., 0.)
+            ]))
+        );
+        assert_eq!(Geometry::<f64>::empty().minimum_rotated_rectangle(), None);
+    }
+}
@@ -1,3 +1,21 @@
+pub mod centroid;
+pub mod concave_hull;
 pub mod convex_hull;
+pub mod coverage;
+pub mod delaunay;
+pub mod equality;
 mod implementation;
+pub mod interior_point;
 pub mod loop_relation;
+pub mod make_valid;
+pub mod minimum_bounding;
+pub mod noding;
+pub mod normalize;
+pub mod offset_curve;
+pub mod polylabel;
+pub mod preserve_topology;
+mod random;
+pub mod simplify;
+pub mod snap_rounding;
+pub mod triangulate;
+pub mod voronoi;
@@ -0,0 +1,215 @@
+use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
+use crate::planar::primitives::{Position, PrecisionModel, Segment, SegmentIntersection};
+use crate::Coordinate;
+
+/**
+ * Split segments wherever they intersect each other, so that any two of the
+ * pieces either meet only at shared endpoints, or are the same.
+ *
+ * Candidate pairs are found with a Flatbush.  An endpoint of one segment that
+ * lies on another is used exactly as the split position, so T-junctions and
+ * overlaps split at the shared vertices; other crossings are computed, and
+ * may be rounded.  The pieces of each segment are returned in order along
+ * it, in the order of the segments.  Zero-length pieces are dropped.
+ *
+ * A rounded crossing can leave a piece slightly off its segment, so that it
+ * crosses another piece.  So the pieces are noded again, until nothing more
+ * is split (or for at most `MAX_NODING_PASSES` passes).
+ */
+pub fn node_segments<C: Coordinate>(segments: &[Segment<C>]) -> Vec<Segment<C>> {
+    node_segments_with_precision(segments, &PrecisionModel::Floating)
+}
+
+/**
+ * Node segments as `node_segments` does, with all positions rounded to a
+ * precision model.  The segment endpoints are rounded first, and then each
+ * split position, so that all the pieces are on the model's grid.
+ *
+ * Rounding a split position moves the pieces off their segment, so they may
+ * cross other pieces; these are split in the following passes.
+ */
+pub fn node_segments_with_precision<C: Coordinate>(
+    segments: &[Segment<C>],
+    precision_model: &PrecisionModel<C>,
+) -> Vec<Segment<C>> {
+    let precise: Vec<Segment<C>> = segments
+        .iter()
+        .map(|s| {
+            Segment::new(
+                precision_model.make_precise_position(s.start),
+                precision_model.make_precise_position(s.end),
+            )
+        })
+        .collect();
+    let mut pieces = _node_once(&precise, precision_model);
+    for _ in 1..MAX_NODING_PASSES {
+        let noded = _node_once(&pieces, precision_model);
+        if noded.len() == pieces.len() {
+            break;
+        }
+        pieces = noded;
+    }
+    pieces
+}
+
+/// The most passes `node_segments` makes over rounded pieces.
+pub const MAX_NODING_PASSES: usize = 8;
+
+fn _node_once<C: Coordinate>(
+    segments: &[Segment<C>],
+    precision_model: &PrecisionModel<C>,
+) -> Vec<Segment<C>> {
+    let mut splits: Vec<Vec<Position<C>>> = vec![Vec::new(); segments.len()];
+    let rtree = Flatbush::new(segments, FLATBUSH_DEFAULT_DEGREE);
+    for (id1, id2) in rtree.find_self_intersection_candidates() {
+        let (seg1, seg2) = (segments[id1], segments[id2]);
+        for position in _find_split_positions(seg1, seg2) {
+            let position = precision_model.make_precise_position(position);
+            splits[id1].push(position);
+            splits[id2].push(position);
+        }
+    }
+
+    let mut pieces = Vec::with_capacity(segments.len());
+    for (segment, mut positions) in segments.iter().zip(splits) {
+        let direction = segment.end - segment.start;
+        let along = |p: &Position<C>| Position::dot(*p - segment.start, direction);
+        positions.sort_by(|a, b| along(a).partial_cmp(&along(b)).unwrap());
+        let mut start = segment.start;
+        for end in positions.into_iter().chain(std::iter::once(segment.end)) {
+            if end != start && end != segment.end {
+                pieces.push(Segment::new(start, end));
+                start = end;
+            }
+        }
+        if start != segment.end {
+            pieces.push(Segment::new(start, segment.end));
+        }
+    }
+    pieces
+}
+
+/// The positions where two segments must be split to node them.
+fn _find_split_positions<C: Coordinate>(seg1: Segment<C>, seg2: Segment<C>) -> Vec<Position<C>> {
+    let shared: Vec<Position<C>> = [
+        (seg1.start, seg2),
+        (seg1.end, seg2),
+        (seg2.start, seg1),
+        (seg2.end, seg1),
+    ]
+    .iter()
+    .filter(|(position, segment)| segment.contains(*position))
+    .map(|&(position, _)| position)
+    .collect();
+    if !shared.is_empty() {
+        return shared;
+    }
+    // Rounding can make the test asymmetric, so a near-touch counts either way.
+    let intersection = match seg1.intersect_segment(seg2) {
+        SegmentIntersection::None => seg2.intersect_segment(seg1),
+        intersection => intersection,
+    };
+    match intersection {
+        SegmentIntersection::Position(p) => vec![_snap_to_endpoints(p, seg1, seg2)],
+        SegmentIntersection::Segment(s) => vec![s.start, s.end],
+        SegmentIntersection::None => Vec::new(),
+    }
+}
+
+/**
+ * A computed crossing that is within rounding error of an endpoint is taken
+ * to be at the endpoint, so near-touches don't leave slivers next to it.
+ */
+fn _snap_to_endpoints<C: Coordinate>(
+    position: Position<C>,
+    seg1: Segment<C>,
+    seg2: Segment<C>,
+) -> Position<C> {
+    let endpoints = [seg1.start, seg1.end, seg2.start, seg2.end];
+    let scale = endpoints
+        .iter()
+        .fold(C::zero(), |m, p| m.max(p.x.abs()).max(p.y.abs()));
+    let tolerance = C::epsilon() * C::from(16).unwrap() * scale;
+    endpoints
+        .iter()
+        .copied()
+        .find(|&e| (e.x - position.x).abs() <= tolerance && (e.y - position.y).abs() <= tolerance)
+        .unwrap_or(position)
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_crossing() {
+        let segments = vec![
+            Segment::from(((0., 0.), (2., 2.))),
+            Segment::from(((0., 2.), (2., 0.))),
+        ];
+        let pieces = node_segments(&segments);
+        assert_eq!(
+            pieces,
+            vec![
+                Segment::from(((0., 0.), (1., 1.))),
+                Segment::from(((1., 1.), (2., 2.))),
+                Segment::from(((0., 2.), (1., 1.))),
+                Segment::from(((1., 1.), (2., 0.))),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_t_junction_and_overlap() {
+        let segments = vec![
+            Segment::from(((0., 0.), (4., 0.))),
+            Segment::from(((1., 0.), (1., 3.))),
+            Segment::from(((3., 0.), (6., 0.))),
+        ];
+        let pieces = node_segments(&segments);
+        assert_eq!(
+            pieces,
+            vec![
+                Segment::from(((0., 0.), (1., 0.))),
+                Segment::from(((1., 0.), (3., 0.))),
+                Segment::from(((3., 0.), (4., 0.))),
+                Segment::from(((1., 0.), (1., 3.))),
+                Segment::from(((3., 0.), (4., 0.))),
+                Segment::from(((4., 0.), (6., 0.))),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_shared_endpoints_unchanged() {
+        let segments = vec![
+            Segment::from(((0., 0.), (1., 0.))),
+            Segment::from(((1., 0.), (1., 1.))),
+        ];
+        assert_eq!(node_segments(&segments), segments);
+    }
+
+    #[test]
+    fn check_crossing_with_precision() {
+        // The crossing is at (1/3, 1/3), which is rounded to the grid.
+        let segments = vec![
+            Segment::from(((0., 0.), (1., 1.))),
+            Segment::from(((0., 0.5), (1., 0.))),
+        ];
+        let pieces = node_segments_with_precision(&segments, &PrecisionModel::fixed(10.).unwrap());
+        assert_eq!(
+            pieces,
+            vec![
+                Segment::from(((0., 0.), (0.3, 0.3))),
+                Segment::from(((0.3, 0.3), (1., 1.))),
+                Segment::from(((0., 0.5), (0.3, 0.3))),
+                Segment::from(((0.3, 0.3), (1., 0.))),
+            ]
+        );
+        // Endpoints are rounded too.
+        let segments = vec![Segment::from(((0.04, 0.), (1.01, 0.)))];
+        let pieces = node_segments_with_precision(&segments, &PrecisionModel::fixed(10.).unwrap());
+        assert_eq!(pieces, vec![Segment::from(((0., 0.), (1., 0.)))]);
+    }
+}
@@ -0,0 +1,352 @@
+use crate::planar::primitives::Position;
+use crate::planar::types::{
+    Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Polygon,
+};
+use crate::Coordinate;
+use ordered_float::FloatIsNan;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/**
+ * Normalize a LineString, so that linestrings with the same positions in the
+ * opposite order, or closed ones with a different start, come out the same.
+ *
+ * An open linestring is reversed if its end is less than its start.  A
+ * closed one is rotated to start at its least position, then reversed if
+ * that makes its second position less.
+ */
+pub fn normalize_line_string<C: Coordinate>(linestring: &LineString<C>) -> LineString<C> {
+    let positions = &linestring.positions;
+    if linestring.is_closed() {
+        let mut rotated = _rotate_to_min(positions);
+        let n = rotated.len();
+        if _cmp_positions(&rotated[n - 2], &rotated[1]) == Ordering::Less {
+            rotated.reverse();
+        }
+        LineString::new(rotated)
+    } else if !positions.is_empty()
+        && _cmp_positions(&positions[positions.len() - 1], &positions[0]) == Ordering::Less
+    {
+        LineString::new(positions.iter().rev().copied().collect())
+    } else {
+        linestring.clone()
+    }
+}
+
+/**
+ * Normalize a polygon ring: rotate it to start at its least position, and
+ * orient it counterclockwise if `ccw`, or else clockwise.  Rings that aren't
+ * closed are left as they are.
+ */
+pub fn normalize_ring<C: Coordinate>(ring: &LineString<C>, ccw: bool) -> LineString<C> {
+    if !ring.is_closed() {
+        return ring.clone();
+    }
+    let mut rotated = _rotate_to_min(&ring.positions);
+    // Reversing a closed ring keeps its first and last positions.
+    if (ccw && ring.is_cw()) || (!ccw && ring.is_ccw()) {
+        rotated.reverse();
+    }
+    LineString::new(rotated)
+}
+
+/**
+ * Normalize a polygon: the exterior winds counterclockwise and the interiors
+ * clockwise, every ring starts at its least position, and the interiors are
+ * sorted by their positions.
+ */
+pub fn normalize_polygon<C: Coordinate>(polygon: &Polygon<C>) -> Polygon<C> {
+    let mut interiors: Vec<LineString<C>> = polygon
+        .interiors
+        .iter()
+        .map(|ring| normalize_ring(ring, false))
+        .collect();
+    interiors.sort_by(|a, b| _cmp_position_slices(&a.positions, &b.positions));
+    Polygon::new(normalize_ring(&polygon.exterior, true), interiors)
+}
+
+/// Normalize each part of a multi-geometry, and sort the parts.
+pub fn normalize_multi_point<C: Coordinate>(multi_point: &MultiPoint<C>) -> MultiPoint<C> {
+    let mut points = multi_point.points.clone();
+    points.sort_by(|a, b| _cmp_positions(&a.0, &b.0));
+    MultiPoint::new(points)
+}
+
+pub fn normalize_multi_line_string<C: Coordinate>(
+    multi_line_string: &MultiLineString<C>,
+) -> MultiLineString<C> {
+    let mut line_strings: Vec<LineString<C>> = multi_line_string
+        .line_strings
+        .iter()
+        .map(normalize_line_string)
+        .collect();
+    line_strings.sort_by(|a, b| _cmp_position_slices(&a.positions, &b.positions));
+    MultiLineString::new(line_strings)
+}
+
+pub fn normalize_multi_polygon<C: Coordinate>(multi_polygon: &MultiPolygon<C>) -> MultiPolygon<C> {
+    let mut polygons: Vec<Polygon<C>> = multi_polygon
+        .polygons
+        .iter()
+        .map(normalize_polygon)
+        .collect();
+    polygons.sort_by(|a, b| _cmp_rings(&_rings_of(a), &_rings_of(b)));
+    MultiPolygon::new(polygons)
+}
+
+/**
+ * A normalized geometry with no NaN coordinates, which can be ordered and
+ * hashed, to dedupe geometries or use them as map keys.
+ *
+ * Two geometries make equal keys when they normalize to the same geometry.
+ * Keys are ordered by geometry type, then by their parts, rings and
+ * positions in order, comparing positions as `SafePosition`s.
+ */
+#[derive(Clone, Debug)]
+pub struct NormalizedGeometry<C: Coordinate> {
+    geometry: Geometry<C>,
+}
+
+impl<C: Coordinate> NormalizedGeometry<C> {
+    /// Normalize the geometry, which fails if it has a NaN coordinate.
+    pub fn new(geometry: &Geometry<C>) -> Result<Self, FloatIsNan> {
+        let geometry = geometry.normalize();
+        for position in geometry.coords_iter() {
+            position.to_hashable()?;
+        }
+        Ok(NormalizedGeometry { geometry })
+    }
+
+    pub fn geometry(&self) -> &Geometry<C> {
+        &self.geometry
+    }
+
+    pub fn into_geometry(self) -> Geometry<C> {
+        self.geometry
+    }
+}
+
+impl<C: Coordinate> PartialEq for NormalizedGeometry<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Coordinate> Eq for NormalizedGeometry<C> {}
+
+impl<C: Coordinate> PartialOrd for NormalizedGeometry<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Coordinate> Ord for NormalizedGeometry<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (rank_1, parts_1) = _geometry_parts(&self.geometry);
+        let (rank_2, parts_2) = _geometry_parts(&other.geometry);
+        rank_1.cmp(&rank_2).then_with(|| {
+            let mut parts = parts_1.iter().zip(&parts_2);
+            parts
+                .find_map(|(rings_1, rings_2)| match _cmp_rings(rings_1, rings_2) {
+                    Ordering::Equal => None,
+                    ordering => Some(ordering),
+                })
+                .unwrap_or_else(|| parts_1.len().cmp(&parts_2.len()))
+        })
+    }
+}
+
+impl<C: Coordinate> Hash for NormalizedGeometry<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (rank, parts) = _geometry_parts(&self.geometry);
+        rank.hash(state);
+        for rings in parts {
+            rings.len().hash(state);
+            for ring in rings {
+                ring.len().hash(state);
+                for position in ring {
+                    // Positions were checked for NaNs when this was made.
+                    position.to_hashable().unwrap().hash(state);
+                }
+            }
+        }
+    }
+}
+
+/**
+ * A geometry as a rank for its type, and its parts, each a list of rings (or
+ * linestrings, or single points) of positions.  This is for comparing
+ * geometries part by part.
+ */
+pub(crate) fn _geometry_parts<C: Coordinate>(
+    geometry: &Geometry<C>,
+) -> (u8, Vec<Vec<&[Position<C>]>>) {
+    match geometry {
+        Geometry::Empty(_) => (0, Vec::new()),
+        Geometry::Point(p) => (1, vec![vec![std::slice::from_ref(&p.0)]]),
+        Geometry::MultiPoint(mp) => (
+            2,
+            mp.points
+                .iter()
+                .map(|p| vec![std::slice::from_ref(&p.0)])
+                .collect(),
+        ),
+        Geometry::LineString(ls) => (3, vec![vec![&ls.positions[..]]]),
+        Geometry::MultiLineString(mls) => (
+            4,
+            mls.line_strings
+                .iter()
+                .map(|ls| vec![&ls.positions[..]])
+                .collect(),
+        ),
+        Geometry::Polygon(p) => (5, vec![_rings_of(p)]),
+        Geometry::MultiPolygon(mp) => (6, mp.polygons.iter().map(_rings_of).collect()),
+    }
+}
+
+/// Rotate a closed ring's positions to start (and end) at the least one.
+fn _rotate_to_min<C: Coordinate>(positions: &[Position<C>]) -> Vec<Position<C>> {
+    let n = positions.len() - 1;
+    let start = (1..n).fold(0, |min, i| {
+        if _cmp_positions(&positions[i], &positions[min]) == Ordering::Less {
+            i
+        } else {
+            min
+        }
+    });
+    let mut rotated = Vec::with_capacity(positions.len());
+    rotated.extend_from_slice(&positions[start..n]);
+    rotated.extend_from_slice(&positions[..=start]);
+    rotated
+}
+
+fn _rings_of<C: Coordinate>(polygon: &Polygon<C>) -> Vec<&[Position<C>]> {
+    std::iter::once(&polygon.exterior)
+        .chain(&polygon.interiors)
+        .map(|ring| &ring.positions[..])
+        .collect()
+}
+
+/// Compare positions by x, then y.  NaNs compare equal to everything.
+fn _cmp_positions<C: Coordinate>(a: &Position<C>, b: &Position<C>) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+fn _cmp_position_slices<C: Coordinate>(a: &[Position<C>], b: &[Position<C>]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(p, q)| _cmp_positions(p, q))
+        .find(|&ordering| ordering != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+fn _cmp_rings<C: Coordinate>(a: &[&[Position<C>]], b: &[&[Position<C>]]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(p, q)| _cmp_position_slices(p, q))
+        .find(|&ordering| ordering != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planar::types::Point;
+    use std::collections::HashSet;
+
+    #[test]
+    fn check_line_string() {
+        let ls = LineString::from(vec![(2., 0.), (1., 1.), (0., 0.)]);
+        assert_eq!(
+            ls.normalize(),
+            LineString::from(vec![(0., 0.), (1., 1.), (2., 0.)])
+        );
+        let closed = LineString::from(vec![(1., 1.), (0., 1.), (0., 0.), (1., 0.), (1., 1.)]);
+        assert_eq!(
+            closed.normalize(),
+            LineString::from(vec![(0., 0.), (0., 1.), (1., 1.), (1., 0.), (0., 0.)])
+        );
+    }
+
+    #[test]
+    fn check_polygon() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(4., 4.), (4., 0.), (0., 0.), (0., 4.), (4., 4.)]),
+            vec![
+                LineString::from(vec![(3., 3.), (2., 3.), (2., 2.), (3., 3.)]),
+                LineString::from(vec![(1., 1.), (1., 2.), (2., 1.), (1., 1.)]),
+            ],
+        );
+        let expected = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 1.), (1., 2.), (2., 1.), (1., 1.)]),
+                LineString::from(vec![(2., 2.), (2., 3.), (3., 3.), (2., 2.)]),
+            ],
+        );
+        assert_eq!(polygon.normalize(), expected);
+        assert_eq!(expected.normalize(), expected);
+    }
+
+    #[test]
+    fn check_multi_geometries() {
+        let mp = MultiPoint::from(vec![(1., 0.), (0., 1.), (0., 0.)]);
+        assert_eq!(
+            mp.normalize(),
+            MultiPoint::from(vec![(0., 0.), (0., 1.), (1., 0.)])
+        );
+        let mls = MultiLineString::new(vec![
+            LineString::from(vec![(3., 0.), (2., 0.)]),
+            LineString::from(vec![(0., 0.), (1., 0.)]),
+        ]);
+        assert_eq!(
+            mls.normalize(),
+            MultiLineString::new(vec![
+                LineString::from(vec![(0., 0.), (1., 0.)]),
+                LineString::from(vec![(2., 0.), (3., 0.)]),
+            ])
+        );
+        let mpoly = MultiPolygon::from(vec![
+            vec![(5., 5.), (6., 5.), (6., 6.), (5., 5.)],
+            vec![(0., 0.), (1., 1.), (1., 0.), (0., 0.)],
+        ]);
+        let normalized = mpoly.normalize();
+        assert_eq!(
+            normalized.polygons[0].exterior.positions[0],
+            (0., 0.).into()
+        );
+        assert!(normalized.polygons.iter().all(|p| p.exterior.is_ccw()));
+    }
+
+    #[test]
+    fn check_keys() {
+        let square_1 = Geometry::from(Polygon::from(vec![
+            (0., 0.),
+            (1., 0.),
+            (1., 1.),
+            (0., 1.),
+            (0., 0.),
+        ]));
+        let square_2 = Geometry::from(Polygon::from(vec![
+            (1., 1.),
+            (1., 0.),
+            (0., 0.),
+            (0., 1.),
+            (1., 1.),
+        ]));
+        let line = Geometry::from(LineString::from(vec![(0., 0.), (1., 1.)]));
+        let keys: HashSet<NormalizedGeometry<f64>> = [&square_1, &square_2, &line]
+            .iter()
+            .map(|g| NormalizedGeometry::new(g).unwrap())
+            .collect();
+        assert_eq!(keys.len(), 2);
+
+        let key_1 = NormalizedGeometry::new(&square_1).unwrap();
+        let key_2 = NormalizedGeometry::new(&line).unwrap();
+        assert!(key_2 < key_1);
+        assert_eq!(key_1.geometry(), &square_1.normalize());
+
+        let nan = Geometry::from(Point::from((f64::NAN, 0.)));
+        assert!(NormalizedGeometry::new(&nan).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
+use crate::planar::primitives::{Position, Segment, SegmentIntersection};
+use crate::planar::types::LineString;
+use crate::Coordinate;
+use std::collections::BTreeMap;
+
+/// Mitered joins longer than this multiple of the offset distance are beveled.
+const MITER_LIMIT: f64 = 5.0;
+
+/**
+ * Find the single-sided offset curve of a LineString.
+ *
+ * A positive distance offsets to the left of the direction of travel, and a
+ * negative distance offsets to the right.  Outside corners are mitered, unless
+ * the miter would extend more than `MITER_LIMIT * |distance|` from the vertex,
+ * in which case they are beveled.  Inside corners are trimmed to the point
+ * where the adjacent offset segments meet.
+ *
+ * Tight bends (where a segment is shorter than the offset distance) make the
+ * raw offset curve loop back on itself.  These loops are cut out, so the
+ * result has no self-intersections that were introduced by the offsetting.
+ *
+ * Repeated positions are ignored.  If the LineString has fewer than two
+ * distinct positions, the result is empty.  If the LineString is closed, the
+ * result is closed as well.
+ */
+pub fn find_offset_curve<C: Coordinate>(linestring: &LineString<C>, distance: C) -> LineString<C> {
+    let mut positions: Vec<Position<C>> = Vec::with_capacity(linestring.num_points());
+    for &position in &linestring.positions {
+        if positions.last() != Some(&position) {
+            positions.push(position);
+        }
+    }
+    if positions.len() < 2 {
+        return LineString::new(Vec::new());
+    }
+    if distance == C::zero() {
+        return LineString::new(positions);
+    }
+
+    let closed = linestring.is_closed();
+    let offset_segments: Vec<Segment<C>> = positions
+        .iter()
+        .zip(positions.iter().skip(1))
+        .map(|(&start, &end)| _offset_segment(Segment::new(start, end), distance))
+        .collect();
+
+    let mut raw: Vec<Position<C>> = Vec::with_capacity(2 * offset_segments.len());
+    if closed {
+        let last = offset_segments[offset_segments.len() - 1];
+        _add_join(&mut raw, positions[0], last, offset_segments[0], distance);
+        // The join might produce several positions; the ring starts at the last.
+        let start = raw.pop().unwrap();
+        raw.clear();
+        raw.push(start);
+    } else {
+        raw.push(offset_segments[0].start);
+    }
+    for (index, pair) in offset_segments.windows(2).enumerate() {
+        _add_join(&mut raw, positions[index + 1], pair[0], pair[1], distance);
+    }
+    if closed {
+        raw.push(raw[0]);
+    } else {
+        raw.push(offset_segments[offset_segments.len() - 1].end);
+    }
+
+    LineString::new(_remove_loops(raw))
+}
+
+/// Translate the segment `distance` along its left normal.
+fn _offset_segment<C: Coordinate>(segment: Segment<C>, distance: C) -> Segment<C> {
+    let direction = segment.end - segment.start;
+    let normal = Position::new(-direction.y, direction.x) / segment.length();
+    let offset = normal * distance;
+    Segment::new(segment.start + offset, segment.end + offset)
+}
+
+/**
+ * Add the positions joining the offsets of two consecutive segments.
+ *
+ * `vertex` is the original position shared by the two segments, and
+ * `before`/`after` are the offset segments on either side of it.
+ */
+fn _add_join<C: Coordinate>(
+    positions: &mut Vec<Position<C>>,
+    vertex: Position<C>,
+    before: Segment<C>,
+    after: Segment<C>,
+    distance: C,
+) {
+    let turn = Position::cross(before.end - before.start, after.end - after.start);
+    if turn == C::zero() {
+        if Position::dot(before.end - before.start, after.end - after.start) > C::zero() {
+            // Straight continuation: the offsets meet exactly.
+            positions.push(before.end);
+        } else {
+            // Full reversal: cap it flat.
+            positions.push(before.end);
+            positions.push(after.start);
+        }
+        return;
+    }
+
+    let is_inside = (turn > C::zero()) == (distance > C::zero());
+    if is_inside {
+        match before.intersect_segment(after) {
+            SegmentIntersection::Position(p) => positions.push(p),
+            _ => {
+                // The offset segments don't reach each other; the resulting
+                // backtrack is a loop that will be removed later.
+                positions.push(before.end);
+                positions.push(after.start);
+            }
+        }
+        return;
+    }
+
+    let miter_limit = C::from(MITER_LIMIT).unwrap() * distance.abs();
+    match _line_intersection(before, after) {
+        Some(miter) if Segment::new(vertex, miter).length() <= miter_limit => {
+            positions.push(miter);
+        }
+        _ => {
+            positions.push(before.end);
+            positions.push(after.start);
+        }
+    }
+}
+
+/// Intersection of the infinite lines through two segments, if not parallel.
+fn _line_intersection<C: Coordinate>(a: Segment<C>, b: Segment<C>) -> Option<Position<C>> {
+    let da = a.end - a.start;
+    let db = b.end - b.start;
+    let da_x_db = Position::cross(da, db);
+    if da_x_db == C::zero() {
+        return None;
+    }
+    let t = Position::cross(b.start - a.start, db) / da_x_db;
+    Some(a.start + da * t)
+}
+
+/**
+ * Cut out the loops in a raw offset curve.
+ *
+ * Walking along the curve, whenever the current segment crosses a later
+ * (non-adjacent) segment, jump directly to the furthest such crossing.
+ */
+fn _remove_loops<C: Coordinate>(raw: Vec<Position<C>>) -> Vec<Position<C>> {
+    let segments: Vec<Segment<C>> = raw
+        .iter()
+        .zip(raw.iter().skip(1))
+        .map(|(&start, &end)| Segment::new(start, end))
+        .collect();
+    let num_segments = segments.len();
+    let is_closed = raw.first() == raw.last();
+
+    // For each segment, the later segments that cross it and where.
+    let mut crossings: BTreeMap<usize, Vec<(usize, Position<C>)>> = BTreeMap::new();
+    let rtree = Flatbush::new_unsorted(&segments, FLATBUSH_DEFAULT_DEGREE);
+    for (low_id, high_id) in rtree.find_self_intersection_candidates() {
+        if high_id == low_id + 1 || (is_closed && low_id == 0 && high_id == num_segments - 1) {
+            continue;
+        }
+        let position = match segments[low_id].intersect_segment(segments[high_id]) {
+            SegmentIntersection::None => continue,
+            SegmentIntersection::Position(p) => p,
+            SegmentIntersection::Segment(s) => s.start,
+        };
+        crossings
+            .entry(low_id)
+            .or_default()
+            .push((high_id, position));
+    }
+    if crossings.is_empty() {
+        return raw;
+    }
+
+    let mut result = vec![raw[0]];
+    let mut index = 0;
+    while index < num_segments {
+        let current = *result.last().unwrap();
+        let segment = segments[index];
+        // Only consider crossings ahead of where we are on this segment.
+        let ahead = |p: Position<C>| {
+            Position::dot(p - current, segment.end - segment.start) > C::zero() || p == current
+        };
+        let jump = crossings.get(&index).and_then(|candidates| {
+            candidates
+                .iter()
+                .filter(|(_, p)| ahead(*p))
+                .max_by_key(|(high_id, _)| *high_id)
+                .copied()
+        });
+        match jump {
+            Some((high_id, position)) => {
+                if position != current {
+                    result.push(position);
+                }
+                index = high_id;
+            }
+            None => {
+                result.push(segment.end);
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_straight_line_left() {
+        let ls = LineString::from(vec![(0., 0.), (2., 0.)]);
+        let offset = find_offset_curve(&ls, 1.);
+        assert_eq!(offset, LineString::from(vec![(0., 1.), (2., 1.)]));
+    }
+
+    #[test]
+    fn check_straight_line_right() {
+        let ls = LineString::from(vec![(0., 0.), (2., 0.)]);
+        let offset = find_offset_curve(&ls, -1.);
+        assert_eq!(offset, LineString::from(vec![(0., -1.), (2., -1.)]));
+    }
+
+    #[test]
+    fn check_repeated_and_collinear_positions() {
+        let ls = LineString::from(vec![(0., 0.), (1., 0.), (1., 0.), (2., 0.)]);
+        let offset = find_offset_curve(&ls, 1.);
+        assert_eq!(offset, LineString::from(vec![(0., 1.), (1., 1.), (2., 1.)]));
+    }
+
+    #[test]
+    fn check_degenerate_is_empty() {
+        let ls = LineString::from(vec![(0., 0.), (0., 0.)]);
+        assert!(find_offset_curve(&ls, 1.).is_empty());
+    }
+
+    #[test]
+    fn check_outside_corner_mitered() {
+        let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+        let offset = find_offset_curve(&ls, -1.);
+        assert_eq!(
+            offset,
+            LineString::from(vec![(0., -1.), (3., -1.), (3., 2.)])
+        );
+    }
+
+    #[test]
+    fn check_inside_corner_trimmed() {
+        let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+        let offset = find_offset_curve(&ls, 1.);
+        assert_eq!(offset, LineString::from(vec![(0., 1.), (1., 1.), (1., 2.)]));
+    }
+
+    #[test]
+    fn check_sharp_outside_corner_beveled() {
+        let ls = LineString::from(vec![(0., 0.), (10., 0.), (0., 0.1)]);
+        let offset = find_offset_curve(&ls, -1.);
+        // A miter would reach ~200 units out, so it must be beveled.
+        assert_eq!(offset.num_points(), 4);
+        assert!(offset.validate().is_ok());
+    }
+
+    #[test]
+    fn check_tight_bend_has_no_loop() {
+        // The middle segment is shorter than the offset distance.
+        let ls = LineString::from(vec![(0., 0.), (4., 0.), (4., 0.5), (0., 0.5)]);
+        let offset = find_offset_curve(&ls, 1.);
+        assert!(offset.validate().is_ok());
+        assert_eq!(offset.positions.first(), Some(&Position::new(0., 1.)));
+        assert_eq!(offset.positions.last(), Some(&Position::new(0., -0.5)));
+    }
+
+    #[test]
+    fn check_closed_square() {
+        let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]);
+        let offset = find_offset_curve(&ls, -1.);
+        assert!(offset.is_closed());
+        assert!(offset.validate().is_ok());
+        assert_eq!(offset.num_points(), 5);
+        assert!(offset.positions.contains(&Position::new(-1., -1.)));
+        assert!(offset.positions.contains(&Position::new(3., 3.)));
+    }
+}
@@ -0,0 +1,205 @@
+use super::interior_point::find_interior_position;
+use crate::planar::primitives::{Envelope, Position, Segment};
+use crate::planar::types::Polygon;
+use crate::Coordinate;
+use ordered_float::OrderedFloat;
+use std::collections::BinaryHeap;
+
+/// A square cell of the quadtree search.
+#[derive(Debug, Clone, Copy)]
+struct Cell<C: Coordinate> {
+    center: Position<C>,
+    half_size: C,
+    /// Signed distance from the center to the boundary, positive inside.
+    distance: C,
+    /// Upper bound on the distance for any position in the cell.
+    max_distance: C,
+}
+
+/**
+ * Find the pole of inaccessibility of a set of polygons: the interior
+ * position farthest from any boundary, along with that distance.
+ *
+ * This is the "polylabel" quadtree search.  The envelope is covered with
+ * square cells, which are kept in a priority queue by the greatest distance
+ * any position in them could have (the distance of the center, plus the
+ * half-diagonal).  The most promising cell is split into four, until no cell
+ * could beat the best found by more than `precision`.
+ *
+ * The polygons are treated as a single area (as for a valid `MultiPolygon`).
+ * If they are empty or have no area, return None.
+ */
+pub fn find_pole_of_inaccessibility<C: Coordinate>(
+    polygons: &[Polygon<C>],
+    precision: C,
+) -> Option<(Position<C>, C)> {
+    let segments: Vec<Segment<C>> = polygons
+        .iter()
+        .flat_map(|p| std::iter::once(&p.exterior).chain(p.interiors.iter()))
+        .flat_map(|ring| ring.segments_iter())
+        .collect();
+    let (min, max) = match Envelope::of(polygons.iter()) {
+        Envelope::Empty => return None,
+        Envelope::Bounds { min, max } => (min, max),
+    };
+    let two = C::one() + C::one();
+    let cell_size = (max.x - min.x).min(max.y - min.y);
+    if cell_size <= C::zero() {
+        return None;
+    }
+    // Avoid endless splitting if the precision is zero (or negative).
+    let precision = precision.max(cell_size * C::epsilon());
+
+    let new_cell = |center: Position<C>, half_size: C| {
+        let distance = _signed_distance(center, &segments);
+        Cell {
+            center,
+            half_size,
+            distance,
+            max_distance: distance + half_size * two.sqrt(),
+        }
+    };
+
+    // Start with the best of the guaranteed interior positions, and the center.
+    let mut best = polygons
+        .iter()
+        .filter_map(find_interior_position)
+        .map(|(position, _)| new_cell(position, C::zero()))
+        .chain(std::iter::once(new_cell((min + max) / two, C::zero())))
+        .max_by_key(|cell| OrderedFloat(cell.distance))?;
+
+    let mut cells: Vec<Cell<C>> = Vec::new();
+    let mut heap = BinaryHeap::new();
+    let half_size = cell_size / two;
+    let mut x = min.x;
+    while x < max.x {
+        let mut y = min.y;
+        while y < max.y {
+            let cell = new_cell(Position::new(x + half_size, y + half_size), half_size);
+            heap.push((OrderedFloat(cell.max_distance), cells.len()));
+            cells.push(cell);
+            y = y + cell_size;
+        }
+        x = x + cell_size;
+    }
+
+    while let Some((OrderedFloat(max_distance), index)) = heap.pop() {
+        let cell = cells[index];
+        if cell.distance > best.distance {
+            best = cell;
+        }
+        if max_distance - best.distance <= precision {
+            // No remaining cell can do better.
+            break;
+        }
+        let half_size = cell.half_size / two;
+        for &(dx, dy) in &[(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let offset = Position::new(C::from(dx).unwrap(), C::from(dy).unwrap()) * half_size;
+            let child = new_cell(cell.center + offset, half_size);
+            heap.push((OrderedFloat(child.max_distance), cells.len()));
+            cells.push(child);
+        }
+    }
+    Some((best.center, best.distance))
+}
+
+/**
+ * Find the distance from the position to the nearest segment, made negative
+ * if the position is outside the rings (by the even-odd rule).
+ */
+fn _signed_distance<C: Coordinate>(position: Position<C>, segments: &[Segment<C>]) -> C {
+    let mut is_inside = false;
+    let mut distance = C::infinity();
+    for segment in segments {
+        let (start, end) = (segment.start, segment.end);
+        if (start.y > position.y) != (end.y > position.y)
+            && position.x < start.x + (position.y - start.y) * (end.x - start.x) / (end.y - start.y)
+        {
+            is_inside = !is_inside;
+        }
+        distance = distance.min(segment.distance_to_position(position));
+    }
+    if is_inside {
+        distance
+    } else {
+        -distance
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::types::{LineString, MultiPolygon, Point};
+
+    #[test]
+    fn check_empty() {
+        let polygon: Polygon<f64> = Polygon::from(LineString::new(Vec::new()));
+        assert_eq!(polygon.pole_of_inaccessibility(0.1), None);
+    }
+
+    #[test]
+    fn check_square() {
+        let polygon = Polygon::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]);
+        assert_eq!(
+            polygon.pole_of_inaccessibility(0.01),
+            Some((Point::from((2., 2.)), 2.))
+        );
+    }
+
+    #[test]
+    fn check_thin_rectangle() {
+        let polygon = Polygon::from(vec![(0., 0.), (10., 0.), (10., 2.), (0., 2.), (0., 0.)]);
+        let (point, distance): (Point<f64>, f64) = polygon.pole_of_inaccessibility(0.01).unwrap();
+        assert!((distance - 1.).abs() <= 0.01);
+        assert!((point.y() - 1.).abs() <= 0.01);
+    }
+
+    #[test]
+    fn check_l_shape() {
+        // The wide bottom arm is 4 tall, the narrow left arm is 2 wide.
+        let polygon = Polygon::from(vec![
+            (0., 0.),
+            (20., 0.),
+            (20., 4.),
+            (2., 4.),
+            (2., 20.),
+            (0., 20.),
+            (0., 0.),
+        ]);
+        let (point, distance): (Point<f64>, f64) = polygon.pole_of_inaccessibility(0.01).unwrap();
+        assert!((distance - 2.).abs() <= 0.01);
+        assert!(point.y() < 4. && point.x() > 2.);
+    }
+
+    #[test]
+    fn check_avoids_hole() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (3., 3.),
+                (7., 3.),
+                (7., 7.),
+                (3., 7.),
+                (3., 3.),
+            ])],
+        );
+        // The corners are best: (t, t) is equally far from the shell and the
+        // hole's corner when t = sqrt(2) * (3 - t).
+        let expected = 3. * 2f64.sqrt() / (1. + 2f64.sqrt());
+        let (point, distance): (Point<f64>, f64) = polygon.pole_of_inaccessibility(0.01).unwrap();
+        assert!((distance - expected).abs() <= 0.01);
+        assert!(point.x() < 3. || point.x() > 7. || point.y() < 3. || point.y() > 7.);
+    }
+
+    #[test]
+    fn check_multipolygon_uses_largest() {
+        let mp = MultiPolygon::from(vec![
+            vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)],
+            vec![(5., 0.), (11., 0.), (11., 6.), (5., 6.), (5., 0.)],
+        ]);
+        let (point, distance): (Point<f64>, f64) = mp.pole_of_inaccessibility(0.01).unwrap();
+        assert!((distance - 3.).abs() <= 0.01);
+        assert!((point.x() - 8.).abs() <= 0.1 && (point.y() - 3.).abs() <= 0.1);
+    }
+}
@@ -0,0 +1,248 @@
+use super::simplify::simplify_rdp_with;
+use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
+use crate::planar::primitives::{Envelope, Position, Segment, SegmentIntersection};
+use crate::Coordinate;
+
+/**
+ * Simplify a set of lines together, without changing their topology.
+ *
+ * Each line is simplified with Ramer–Douglas–Peucker, keeping at least the
+ * corresponding number of `min_positions`.  A run of positions is only
+ * replaced by a shortcut segment if:
+ * 1. The shortcut doesn't intersect any segment of any line (including this
+ *    one) outside the run, except at the shortcut's own endpoints, and
+ * 2. No position of any line outside the run lies in the region between the
+ *    run and the shortcut.
+ *
+ * The first condition prevents new crossings; the second prevents the
+ * shortcut from jumping over another line (eg, a hole escaping its shell).
+ * Both are checked against the original lines, with a `Flatbush` over all of
+ * their segments.
+ */
+pub fn simplify_lines_preserve_topology<C: Coordinate>(
+    lines: &[&[Position<C>]],
+    min_positions: &[usize],
+    tolerance: C,
+) -> Vec<Vec<Position<C>>> {
+    // All segments, and the (line, segment index) they came from.
+    let mut segments: Vec<Segment<C>> = Vec::new();
+    let mut owners: Vec<(usize, usize)> = Vec::new();
+    for (line_id, positions) in lines.iter().enumerate() {
+        for (seg_id, pair) in positions.windows(2).enumerate() {
+            segments.push(Segment::new(pair[0], pair[1]));
+            owners.push((line_id, seg_id));
+        }
+    }
+    let rtree = Flatbush::new(&segments, FLATBUSH_DEFAULT_DEGREE);
+
+    lines
+        .iter()
+        .zip(min_positions)
+        .enumerate()
+        .map(|(line_id, (positions, &min))| {
+            simplify_rdp_with(positions, tolerance, min, |start, end| {
+                _can_shortcut(&rtree, &segments, &owners, line_id, positions, start, end)
+            })
+        })
+        .collect()
+}
+
+fn _can_shortcut<C: Coordinate>(
+    rtree: &Flatbush<C>,
+    segments: &[Segment<C>],
+    owners: &[(usize, usize)],
+    line_id: usize,
+    positions: &[Position<C>],
+    start: usize,
+    end: usize,
+) -> bool {
+    let shortcut = Segment::new(positions[start], positions[end]);
+    let run = &positions[start..=end];
+    let run_envelope = Envelope::of(run.iter());
+    let is_in_run = |owner: (usize, usize)| owner.0 == line_id && start <= owner.1 && owner.1 < end;
+
+    for seg_id in rtree.find_intersection_candidates(run_envelope) {
+        if is_in_run(owners[seg_id]) {
+            continue;
+        }
+        let other = segments[seg_id];
+        match shortcut.intersect_segment(other) {
+            SegmentIntersection::None => (),
+            SegmentIntersection::Segment(_) => return false,
+            SegmentIntersection::Position(p) => {
+                let at_shortcut_end = p == shortcut.start || p == shortcut.end;
+                let at_other_end = p == other.start || p == other.end;
+                if !(at_shortcut_end && at_other_end) {
+                    return false;
+                }
+            }
+        }
+        for &vertex in &[other.start, other.end] {
+            if vertex != shortcut.start
+                && vertex != shortcut.end
+                && _is_in_or_on_region(vertex, run)
+            {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/**
+ * Check if the position is inside or on the boundary of the region enclosed
+ * by the run and the segment joining its ends.
+ *
+ * This keeps holes inside shells while simplifying.  The run and its
+ * shortcut needn't form a valid loop, since the run may cross the shortcut,
+ * so the region is found by a non-zero winding number rather than with
+ * `find_loop_loop_relation`.  The simplified rings are then related with
+ * `find_loop_loop_relation` by `validate`, in `simplify_preserve_topology`.
+ */
+fn _is_in_or_on_region<C: Coordinate>(position: Position<C>, run: &[Position<C>]) -> bool {
+    let closing = Segment::new(run[run.len() - 1], run[0]);
+    let boundary = run
+        .windows(2)
+        .map(|pair| Segment::new(pair[0], pair[1]))
+        .chain(std::iter::once(closing));
+    let mut winding_number = 0;
+    for seg in boundary {
+        if seg.contains(position) {
+            return true;
+        }
+        winding_number += Segment::find_winding_number(position, seg);
+    }
+    winding_number != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planar::algorithms::random::Lcg;
+    use crate::planar::types::{LineString, Polygon};
+
+    #[test]
+    fn check_unobstructed_matches_rdp() {
+        let line: Vec<Position<f64>> = vec![(0., 0.), (1., 0.1), (2., 0.)]
+            .into_iter()
+            .map(Position::from)
+            .collect();
+        let result = simplify_lines_preserve_topology(&[&line], &[2], 1.0);
+        assert_eq!(result, vec![vec![line[0], line[2]]]);
+    }
+
+    #[test]
+    fn check_blocked_by_other_line() {
+        let line: Vec<Position<f64>> = vec![(0., 0.), (1., 1.), (2., 0.)]
+            .into_iter()
+            .map(Position::from)
+            .collect();
+        // A little line just under the bump, which a shortcut would jump over.
+        let other: Vec<Position<f64>> = vec![(0.9, 0.5), (1.1, 0.5)]
+            .into_iter()
+            .map(Position::from)
+            .collect();
+        let result = simplify_lines_preserve_topology(&[&line, &other], &[2, 2], 2.0);
+        assert_eq!(result[0], line);
+        assert_eq!(result[1], other);
+    }
+
+    #[test]
+    fn check_hole_stays_in_shell() {
+        // A shallow bump in the exterior holds a small hole.
+        let polygon = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (5., -1.),
+                (6., 0.),
+                (10., 0.),
+                (10., 10.),
+                (0., 10.),
+                (0., 0.),
+            ]),
+            vec![LineString::from(vec![
+                (4.9, -0.3),
+                (5.1, -0.3),
+                (5., -0.6),
+                (4.9, -0.3),
+            ])],
+        );
+        assert!(polygon.validate().is_ok());
+        // Plain simplification drops the bump, leaving the hole outside.
+        assert!(polygon.simplify(2.0).validate().is_err());
+        let simplified = polygon.simplify_preserve_topology(2.0);
+        assert!(simplified.validate().is_ok());
+        assert!(simplified
+            .exterior
+            .positions
+            .contains(&Position::new(5., -1.)));
+    }
+
+    #[test]
+    fn check_no_self_crossing() {
+        // The tail pokes up under the bump; dropping the bump crosses it.
+        let ls = LineString::from(vec![
+            (0., 0.),
+            (4., 0.),
+            (5., 1.),
+            (6., 0.),
+            (10., 0.),
+            (10., -5.),
+            (5., -5.),
+            (5., 0.5),
+        ]);
+        assert!(ls.validate().is_ok());
+        assert!(ls.simplify(3.0).validate().is_err());
+        let simplified = ls.simplify_preserve_topology(3.0);
+        assert!(simplified.validate().is_ok());
+        assert!(simplified.num_points() < ls.num_points());
+    }
+
+    #[test]
+    fn check_random_polygons() {
+        // Star-shaped shells with holes near them.  The simplified rings,
+        // without falling back to the input, must still form a valid polygon.
+        let mut rng = Lcg::new(2024);
+        let mut star = |center: Position<f64>, min: f64, max: f64, n: usize| {
+            let mut positions: Vec<Position<f64>> = (0..n)
+                .map(|i| {
+                    let angle = std::f64::consts::PI * 2. * i as f64 / n as f64;
+                    let radius = min + (max - min) * rng.next_below(1000) as f64 / 1000.;
+                    center + Position::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect();
+            positions.push(positions[0]);
+            positions
+        };
+        let mut num_checked = 0;
+        for i in 0..200 {
+            let exterior = star(Position::new(0., 0.), 6., 10., 40);
+            let offset = 3. + (i % 3) as f64;
+            let holes = [
+                star(Position::new(offset, 0.), 0.5, 2., 12),
+                star(Position::new(-offset, 0.), 0.5, 2., 12),
+            ];
+            let polygon = Polygon::new(
+                LineString::new(exterior.clone()),
+                holes.iter().cloned().map(LineString::new).collect(),
+            );
+            if polygon.validate().is_err() {
+                continue;
+            }
+            num_checked += 1;
+            let mut rings: Vec<&[Position<f64>]> = vec![&exterior];
+            rings.extend(holes.iter().map(|h| &h[..]));
+            for &tolerance in &[0.5, 2., 8.] {
+                let mut simplified =
+                    simplify_lines_preserve_topology(&rings, &[4, 4, 4], tolerance)
+                        .into_iter()
+                        .map(LineString::new);
+                let result = Polygon::new(simplified.next().unwrap(), simplified.collect());
+                assert!(result.validate().is_ok(), "{:?}", result.validate());
+                assert!(result.exterior.num_points() < exterior.len());
+            }
+        }
+        assert!(num_checked > 100);
+    }
+}
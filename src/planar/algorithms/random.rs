@@ -0,0 +1,37 @@
+/**
+ * A linear congruential generator, with Knuth's MMIX constants.
+ *
+ * This is not a good source of randomness, but it is deterministic and
+ * cheap, which is all that shuffling inputs and generating test data need.
+ */
+pub(crate) struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Lcg { state: seed }
+    }
+
+    /// The next 31 bits, from the high bits of the state.
+    pub(crate) fn next_bits(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.state >> 33
+    }
+
+    /// A number in 0..n.
+    pub(crate) fn next_below(&mut self, n: usize) -> usize {
+        (self.next_bits() % n as u64) as usize
+    }
+}
+
+/// A Fisher-Yates shuffle, with a fixed seed.
+pub(crate) fn shuffle<T>(items: &mut [T]) {
+    let mut rng = Lcg::new(0x853c_49e6_748f_ea9b);
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.next_below(i + 1));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::planar::primitives::{Position, Segment, Triangle};
+use crate::Coordinate;
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Open LineStrings can't be simplified below 2 positions.
+pub(crate) const MIN_LINE_POSITIONS: usize = 2;
+/// Rings (closed LineStrings) can't be simplified below 4 positions.
+pub(crate) const MIN_RING_POSITIONS: usize = 4;
+
+/**
+ * Simplify a sequence of positions with the Ramer–Douglas–Peucker algorithm.
+ *
+ * Positions that are within `tolerance` of the simplified line are removed.
+ * The first and last positions are always kept, so closed sequences stay
+ * closed.  At least `min_positions` positions are kept (if there are that
+ * many): the intervals with the largest deviations are split even if that
+ * deviation is within tolerance.
+ *
+ * This is the iterative form of the algorithm: the interval with the largest
+ * deviation is always split first.  This is equivalent to the recursive form,
+ * but lets us honor `min_positions`.
+ */
+pub fn simplify_rdp<C: Coordinate>(
+    positions: &[Position<C>],
+    tolerance: C,
+    min_positions: usize,
+) -> Vec<Position<C>> {
+    simplify_rdp_with(positions, tolerance, min_positions, |_, _| true)
+}
+
+/**
+ * Ramer–Douglas–Peucker simplification with a veto on each shortcut.
+ *
+ * This is `simplify_rdp`, except that before the positions strictly between
+ * `start` and `end` are dropped, `can_shortcut(start, end)` is called.  If it
+ * returns false, the interval is split as if its deviation were too large.
+ */
+pub(crate) fn simplify_rdp_with<C, F>(
+    positions: &[Position<C>],
+    tolerance: C,
+    min_positions: usize,
+    mut can_shortcut: F,
+) -> Vec<Position<C>>
+where
+    C: Coordinate,
+    F: FnMut(usize, usize) -> bool,
+{
+    let len = positions.len();
+    if len <= min_positions.max(2) {
+        return positions.to_vec();
+    }
+
+    let mut keep = vec![false; len];
+    keep[0] = true;
+    keep[len - 1] = true;
+    let mut num_kept = 2;
+
+    // Heap of (max deviation, start, end, index of max deviation).
+    let mut heap = BinaryHeap::new();
+    if let Some(entry) = _find_farthest(positions, 0, len - 1) {
+        heap.push(entry);
+    }
+    while let Some((OrderedFloat(deviation), start, end, index)) = heap.pop() {
+        if deviation <= tolerance && num_kept >= min_positions && can_shortcut(start, end) {
+            continue;
+        }
+        keep[index] = true;
+        num_kept += 1;
+        if let Some(entry) = _find_farthest(positions, start, index) {
+            heap.push(entry);
+        }
+        if let Some(entry) = _find_farthest(positions, index, end) {
+            heap.push(entry);
+        }
+    }
+
+    positions
+        .iter()
+        .zip(keep)
+        .filter(|(_, k)| *k)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// Find the position strictly between start and end farthest from their segment.
+fn _find_farthest<C: Coordinate>(
+    positions: &[Position<C>],
+    start: usize,
+    end: usize,
+) -> Option<(OrderedFloat<C>, usize, usize, usize)> {
+    let segment = Segment::new(positions[start], positions[end]);
+    (start + 1..end)
+        .map(|index| {
+            let distance = segment.distance_to_position(positions[index]);
+            (OrderedFloat(distance), start, end, index)
+        })
+        .max_by_key(|&(distance, _, _, _)| distance)
+}
+
+/**
+ * Simplify a sequence of positions with the Visvalingam–Whyatt algorithm.
+ *
+ * Repeatedly remove the interior position whose triangle with its neighbors
+ * has the smallest area, as long as that area is less than `area_threshold`.
+ * The first and last positions are always kept, so closed sequences stay
+ * closed.  No more than `len - min_positions` positions are removed.
+ */
+pub fn simplify_vw<C: Coordinate>(
+    positions: &[Position<C>],
+    area_threshold: C,
+    min_positions: usize,
+) -> Vec<Position<C>> {
+    let len = positions.len();
+    if len <= min_positions.max(2) {
+        return positions.to_vec();
+    }
+
+    // Doubly-linked list over the remaining positions.
+    let mut previous: Vec<usize> = (0..len).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (0..len).map(|i| (i + 1).min(len - 1)).collect();
+    let mut removed = vec![false; len];
+    let mut num_remaining = len;
+
+    let area_of = |prev: usize, index: usize, nxt: usize| {
+        Triangle::new(positions[prev], positions[index], positions[nxt]).area()
+    };
+
+    // Min-heap of (area, index, prev, next); entries are stale if the
+    // neighbors have changed since they were pushed.
+    let mut heap = BinaryHeap::new();
+    for index in 1..len - 1 {
+        let area = area_of(index - 1, index, index + 1);
+        heap.push(Reverse((OrderedFloat(area), index, index - 1, index + 1)));
+    }
+
+    while let Some(Reverse((OrderedFloat(area), index, prev, nxt))) = heap.pop() {
+        if num_remaining <= min_positions || area >= area_threshold {
+            break;
+        }
+        if removed[index] || previous[index] != prev || next[index] != nxt {
+            continue;
+        }
+        removed[index] = true;
+        num_remaining -= 1;
+        next[prev] = nxt;
+        previous[nxt] = prev;
+        if prev != 0 {
+            let prev_prev = previous[prev];
+            // Don't let an area drop below the one just removed, so that
+            // removal order stays monotonic.
+            let new_area = area_of(prev_prev, prev, nxt).max(area);
+            heap.push(Reverse((OrderedFloat(new_area), prev, prev_prev, nxt)));
+        }
+        if nxt != len - 1 {
+            let next_next = next[nxt];
+            let new_area = area_of(prev, nxt, next_next).max(area);
+            heap.push(Reverse((OrderedFloat(new_area), nxt, prev, next_next)));
+        }
+    }
+
+    positions
+        .iter()
+        .zip(removed)
+        .filter(|(_, r)| !*r)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planar::types::{Geometry, LineString, Point, Polygon};
+
+    fn to_positions(coords: Vec<(f64, f64)>) -> Vec<Position<f64>> {
+        coords.into_iter().map(Position::from).collect()
+    }
+
+    #[test]
+    fn check_rdp_removes_small_deviations() {
+        let positions = to_positions(vec![
+            (0., 0.),
+            (1., 0.1),
+            (2., -0.1),
+            (3., 5.),
+            (4., 6.),
+            (5., 7.),
+        ]);
+        let result = simplify_rdp(&positions, 1.0, MIN_LINE_POSITIONS);
+        assert_eq!(
+            result,
+            to_positions(vec![(0., 0.), (2., -0.1), (3., 5.), (5., 7.)])
+        );
+    }
+
+    #[test]
+    fn check_rdp_straight_line() {
+        let positions = to_positions(vec![(0., 0.), (1., 1.), (2., 2.), (3., 3.)]);
+        let result = simplify_rdp(&positions, 0.1, MIN_LINE_POSITIONS);
+        assert_eq!(result, to_positions(vec![(0., 0.), (3., 3.)]));
+    }
+
+    #[test]
+    fn check_rdp_zero_tolerance_keeps_corners() {
+        let positions = to_positions(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        let result = simplify_rdp(&positions, 0.0, MIN_LINE_POSITIONS);
+        assert_eq!(result, positions);
+    }
+
+    #[test]
+    fn check_rdp_ring_keeps_four() {
+        let positions = to_positions(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+        let result = simplify_rdp(&positions, 10.0, MIN_RING_POSITIONS);
+        assert_eq!(result.len(), 4);
+        assert_eq!(result.first(), result.last());
+    }
+
+    #[test]
+    fn check_vw_removes_small_triangles() {
+        let positions = to_positions(vec![(0., 0.), (1., 0.1), (2., 0.), (2., 2.), (0., 2.)]);
+        let result = simplify_vw(&positions, 0.5, MIN_LINE_POSITIONS);
+        assert_eq!(
+            result,
+            to_positions(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)])
+        );
+    }
+
+    #[test]
+    fn check_vw_ring_keeps_four() {
+        let positions = to_positions(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+        let result = simplify_vw(&positions, 10.0, MIN_RING_POSITIONS);
+        assert_eq!(result.len(), 4);
+        assert_eq!(result.first(), result.last());
+    }
+
+    #[test]
+    fn check_vw_short_input_unchanged() {
+        let positions = to_positions(vec![(0., 0.), (1., 1.)]);
+        assert_eq!(simplify_vw(&positions, 10.0, MIN_LINE_POSITIONS), positions);
+    }
+
+    #[test]
+    fn check_polygon_rings_stay_closed() {
+        let polygon = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (5., 0.1),
+                (10., 0.),
+                (10., 10.),
+                (0., 10.),
+                (0., 0.),
+            ]),
+            vec![LineString::from(vec![
+                (4., 4.),
+                (4., 5.),
+                (5., 5.),
+                (5., 4.),
+                (4., 4.),
+            ])],
+        );
+        let simplified = polygon.simplify(2.0);
+        assert_eq!(
+            simplified.exterior,
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)])
+        );
+        assert_eq!(simplified.interiors[0].num_points(), 4);
+        assert!(simplified.interiors[0].is_closed());
+    }
+
+    #[test]
+    fn check_geometry_point_unchanged() {
+        let geometry = Geometry::from(Point::from((1., 2.)));
+        assert_eq!(geometry.simplify_vw(1.), geometry);
+    }
+}
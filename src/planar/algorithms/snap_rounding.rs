@@ -0,0 +1,319 @@
+use super::noding::node_segments_with_precision;
+use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
+use crate::planar::primitives::{
+    Envelope, HasEnvelope, Position, PrecisionModel, SafePosition, Segment, SegmentIntersection,
+};
+use crate::Coordinate;
+use std::collections::{HashMap, HashSet};
+
+/**
+ * Node segments by snap rounding them to the grid of a fixed precision model.
+ *
+ * Each grid position is the center of a pixel, the size of a grid cell.
+ * The pixels containing a segment endpoint or an intersection of two
+ * segments are hot.  Each segment is replaced by the path through the
+ * centers of all the hot pixels it passes through, in order.  Unlike
+ * splitting at rounded crossings, this can't create new crossings, so the
+ * result is fully noded: any two pieces either meet only at shared
+ * endpoints, or are the same.  Every piece is on the grid, and is within
+ * half a pixel of its segment.
+ *
+ * The pieces of each segment are returned in order along it, in the order of
+ * the segments, as `node_segments` does.  Segments that round to a single
+ * position, or that have non-finite coordinates, are dropped.
+ *
+ * A floating model has no grid to snap to, so then the segments are noded
+ * with `node_segments_with_precision` instead.
+ */
+pub fn snap_round_segments<C: Coordinate>(
+    segments: &[Segment<C>],
+    precision_model: &PrecisionModel<C>,
+) -> Vec<Segment<C>> {
+    let grid_size = match precision_model.grid_size() {
+        Some(grid_size) => grid_size,
+        None => return node_segments_with_precision(segments, precision_model),
+    };
+    let round = |p: Position<C>| precision_model.make_precise_position(p);
+    let segments: Vec<Segment<C>> = segments
+        .iter()
+        .copied()
+        .filter(|s| s.start.validate().is_ok() && s.end.validate().is_ok())
+        .filter(|s| round(s.start) != round(s.end))
+        .collect();
+
+    // The hot pixels each segment must pass through: its own endpoints, and
+    // its intersections with other segments.
+    let mut nodes: Vec<Vec<Position<C>>> = segments
+        .iter()
+        .map(|s| vec![round(s.start), round(s.end)])
+        .collect();
+    let rtree = Flatbush::new(&segments, FLATBUSH_DEFAULT_DEGREE);
+    for (id1, id2) in rtree.find_self_intersection_candidates() {
+        let positions = match segments[id1].intersect_segment(segments[id2]) {
+            SegmentIntersection::None => continue,
+            SegmentIntersection::Position(p) => vec![p],
+            SegmentIntersection::Segment(s) => vec![s.start, s.end],
+        };
+        for position in positions {
+            nodes[id1].push(round(position));
+            nodes[id2].push(round(position));
+        }
+    }
+
+    let mut seen: HashSet<SafePosition<C>> = HashSet::new();
+    let hot_pixels: Vec<Position<C>> = nodes
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|p| match p.to_hashable() {
+            Ok(key) => seen.insert(key),
+            Err(_) => false,
+        })
+        .collect();
+    let half = grid_size / (C::one() + C::one());
+    let corner = Position::new(half, half);
+    let pixel_envelopes: Vec<Envelope<C>> = hot_pixels
+        .iter()
+        .map(|&center| Envelope::new(center - corner, center + corner))
+        .collect();
+    let pixel_rtree = Flatbush::new(&pixel_envelopes, FLATBUSH_DEFAULT_DEGREE);
+
+    let mut pieces = Vec::with_capacity(segments.len());
+    for (segment, nodes) in segments.iter().zip(nodes) {
+        let path = _snap_path(*segment, nodes, &hot_pixels, &pixel_rtree, half);
+        pieces.extend(path.windows(2).map(|p| Segment::new(p[0], p[1])));
+    }
+    pieces
+}
+
+/**
+ * The centers of the hot pixels the segment passes through, from its rounded
+ * start to its rounded end.  `nodes` are the pixels it must pass through,
+ * starting with its rounded endpoints.
+ */
+fn _snap_path<C: Coordinate>(
+    segment: Segment<C>,
+    nodes: Vec<Position<C>>,
+    hot_pixels: &[Position<C>],
+    pixel_rtree: &Flatbush<C>,
+    half: C,
+) -> Vec<Position<C>> {
+    let (start, end) = (nodes[0], nodes[1]);
+    let direction = segment.end - segment.start;
+    let length_2 = Position::dot(direction, direction);
+    // Where the segment enters each pixel, keeping the first entry.
+    let mut entries: HashMap<SafePosition<C>, (C, Position<C>)> = HashMap::new();
+    let mut add = |center: Position<C>, t: C| {
+        if center == start || center == end {
+            return;
+        }
+        if let Ok(key) = center.to_hashable() {
+            let entry = entries.entry(key).or_insert((t, center));
+            if t < entry.0 {
+                entry.0 = t;
+            }
+        }
+    };
+    for id in pixel_rtree.find_intersection_candidates(segment.envelope()) {
+        if let Some(t) = _pixel_entry(segment, hot_pixels[id], half) {
+            add(hot_pixels[id], t);
+        }
+    }
+    // An intersection can be rounded into a pixel that the segment only
+    // passes next to; it must still be a node of both segments.
+    for &center in &nodes[2..] {
+        let t = Position::dot(center - segment.start, direction) / length_2;
+        add(center, _pixel_entry(segment, center, half).unwrap_or(t));
+    }
+
+    let mut middle: Vec<(C, Position<C>)> = entries.into_values().collect();
+    middle.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap()
+            .then(a.1.partial_cmp(&b.1).unwrap())
+    });
+    std::iter::once(start)
+        .chain(middle.into_iter().map(|(_, center)| center))
+        .chain(std::iter::once(end))
+        .collect()
+}
+
+/**
+ * Where the segment enters the pixel around `center`, as a fraction of the
+ * way along it, or None if it doesn't pass through the pixel.  A pixel
+ * includes its left and bottom edges, but not its right and top ones, so a
+ * segment passing through a grid corner is only in one of the pixels there.
+ */
+fn _pixel_entry<C: Coordinate>(segment: Segment<C>, center: Position<C>, half: C) -> Option<C> {
+    let direction = segment.end - segment.start;
+    let (min, max) = (
+        center - Position::new(half, half),
+        center + Position::new(half, half),
+    );
+    // Clip the segment to the closed pixel, one axis at a time.
+    let (mut t_enter, mut t_exit) = (C::zero(), C::one());
+    let axes = [
+        (segment.start.x, direction.x, min.x, max.x),
+        (segment.start.y, direction.y, min.y, max.y),
+    ];
+    for &(start, delta, low, high) in &axes {
+        if delta == C::zero() {
+            if start < low || start > high {
+                return None;
+            }
+            continue;
+        }
+        let (t_low, t_high) = ((low - start) / delta, (high - start) / delta);
+        let (t_min, t_max) = if delta > C::zero() {
+            (t_low, t_high)
+        } else {
+            (t_high, t_low)
+        };
+        t_enter = t_enter.max(t_min);
+        t_exit = t_exit.min(t_max);
+    }
+    if t_enter > t_exit {
+        return None;
+    }
+    // The clipped part must not be only on the right or top edge.
+    let (enter, exit) = (
+        segment.start + direction * t_enter,
+        segment.start + direction * t_exit,
+    );
+    if (enter.x >= max.x && exit.x >= max.x) || (enter.y >= max.y && exit.y >= max.y) {
+        return None;
+    }
+    Some(t_enter)
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::algorithms::random::Lcg;
+
+    /// Check any two pieces only meet at shared endpoints, or are the same.
+    fn assert_fully_noded(pieces: &[Segment<f64>]) {
+        for (i, s1) in pieces.iter().enumerate() {
+            for s2 in &pieces[i + 1..] {
+                let reversed = Segment::new(s2.end, s2.start);
+                if s1 == s2 || *s1 == reversed {
+                    continue;
+                }
+                match s1.intersect_segment(*s2) {
+                    SegmentIntersection::None => (),
+                    SegmentIntersection::Position(p) => {
+                        let shared = p == s1.start || p == s1.end;
+                        assert!(
+                            shared && (p == s2.start || p == s2.end),
+                            "{:?} {:?}",
+                            s1,
+                            s2
+                        );
+                    }
+                    SegmentIntersection::Segment(_) => panic!("{:?} overlaps {:?}", s1, s2),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_crossing() {
+        // The crossing at (1/3, 1/3) is snapped to (0.3, 0.3).
+        let segments = vec![
+            Segment::from(((0., 0.), (1., 1.))),
+            Segment::from(((0., 0.5), (1., 0.))),
+        ];
+        let pieces = snap_round_segments(&segments, &PrecisionModel::fixed(10.).unwrap());
+        assert_eq!(
+            pieces,
+            vec![
+                Segment::from(((0., 0.), (0.3, 0.3))),
+                Segment::from(((0.3, 0.3), (1., 1.))),
+                Segment::from(((0., 0.5), (0.3, 0.3))),
+                Segment::from(((0.3, 0.3), (1., 0.))),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_passes_through_hot_pixel() {
+        // The vertical segment's endpoint makes (1, 0) hot, and the nearly
+        // horizontal one passes through that pixel, so it is snapped to it.
+        let segments = vec![
+            Segment::from(((0., 0.1), (2., -0.1))),
+            Segment::from(((1., 0.2), (1., 2.))),
+        ];
+        let pieces = snap_round_segments(&segments, &PrecisionModel::fixed(1.).unwrap());
+        assert_eq!(
+            pieces,
+            vec![
+                Segment::from(((0., 0.), (1., 0.))),
+                Segment::from(((1., 0.), (2., 0.))),
+                Segment::from(((1., 0.), (1., 2.))),
+            ]
+        );
+        assert_fully_noded(&pieces);
+    }
+
+    #[test]
+    fn check_collapse_and_floating() {
+        let segments = vec![
+            Segment::from(((0., 0.), (0.01, 0.01))),
+            Segment::from(((0., 0.), (f64::NAN, 1.))),
+        ];
+        assert!(snap_round_segments(&segments, &PrecisionModel::fixed(10.).unwrap()).is_empty());
+
+        let segments = vec![
+            Segment::from(((0., 0.), (2., 2.))),
+            Segment::from(((0., 2.), (2., 0.))),
+        ];
+        assert_eq!(
+            snap_round_segments(&segments, &PrecisionModel::Floating),
+            node_segments_with_precision(&segments, &PrecisionModel::Floating)
+        );
+    }
+
+    #[test]
+    fn check_pixel_entry() {
+        let segment = Segment::from(((0., 0.), (4., 0.)));
+        assert_eq!(
+            _pixel_entry(segment, Position::new(1., 0.), 0.5),
+            Some(0.125)
+        );
+        assert_eq!(_pixel_entry(segment, Position::new(1., 1.), 0.5), None);
+        // Only the bottom edge of a pixel is in it, not the top one.
+        assert_eq!(
+            _pixel_entry(segment, Position::new(1., 0.5), 0.5),
+            Some(0.125)
+        );
+        assert_eq!(_pixel_entry(segment, Position::new(1., -0.5), 0.5), None);
+        // The pixels to the left and below of (1, 1) only have the corner
+        // the segment passes through on their right or top edges.
+        let diagonal = Segment::from(((0., 0.), (1., 1.)));
+        let in_pixels: Vec<bool> = [(0., 0.), (1., 0.), (0., 1.), (1., 1.)]
+            .iter()
+            .map(|&c| _pixel_entry(diagonal, Position::from(c), 0.5).is_some())
+            .collect();
+        assert_eq!(in_pixels, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn check_random_segments() {
+        // Many random segments, snapped to a coarse grid, must be fully noded.
+        let mut rng = Lcg::new(54321);
+        let mut next = || rng.next_below(10_000) as f64 / 1000.;
+        let segments: Vec<Segment<f64>> = (0..60)
+            .map(|_| Segment::new(Position::new(next(), next()), Position::new(next(), next())))
+            .collect();
+        for &scale in &[1., 4., 100.] {
+            let model = PrecisionModel::fixed(scale).unwrap();
+            let pieces = snap_round_segments(&segments, &model);
+            assert!(pieces
+                .iter()
+                .all(|s| model.make_precise_position(s.start) == s.start
+                    && model.make_precise_position(s.end) == s.end));
+            assert_fully_noded(&pieces);
+        }
+    }
+}
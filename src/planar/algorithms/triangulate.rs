@@ -0,0 +1,572 @@
+use crate::planar::primitives::{incircle, orient2d, Position, Triangle};
+use crate::planar::types::{LineString, Polygon};
+use crate::Coordinate;
+use std::collections::{HashMap, HashSet};
+
+/**
+ * Triangulate a polygon by ear clipping.
+ *
+ * This is the algorithm of the Earcut library: each hole is bridged to the
+ * exterior, joining them into a single (weakly simple) ring, and then "ears"
+ * (convex corners with no other positions inside) are clipped off one at a
+ * time.  It is fast, but the triangles can be long and thin.
+ *
+ * The triangles are counter-clockwise, and exactly cover the polygon (with
+ * the holes excluded).  Repeated and collinear positions are skipped.
+ */
+pub fn triangulate_polygon_ear_clipping<C: Coordinate>(polygon: &Polygon<C>) -> Vec<Triangle<C>> {
+    let (positions, triangles) = _ear_clip(polygon);
+    _to_triangles(&positions, &triangles)
+}
+
+/**
+ * Find the constrained Delaunay triangulation of a polygon.
+ *
+ * This starts with the ear clipping triangulation, and then flips every edge
+ * that isn't part of a ring, and fails the Delaunay condition, until none are
+ * left.  The result is as close to Delaunay as the ring edges allow, which
+ * avoids thin triangles where possible.
+ *
+ * The triangles are counter-clockwise, and exactly cover the polygon (with
+ * the holes excluded).  Repeated and collinear positions are skipped.
+ */
+pub fn triangulate_polygon<C: Coordinate>(polygon: &Polygon<C>) -> Vec<Triangle<C>> {
+    let (positions, mut triangles) = _ear_clip(polygon);
+
+    let mut constraints: HashSet<(usize, usize)> = HashSet::new();
+    let mut offset = 0;
+    for ring in std::iter::once(&polygon.exterior).chain(polygon.interiors.iter()) {
+        let len = _open_ring_len(ring);
+        for i in 0..len {
+            constraints.insert(_edge_key(offset + i, offset + (i + 1) % len));
+        }
+        offset += len;
+    }
+
+    // The triangle to the left of each directed edge.
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            edges.insert((triangle[k], triangle[(k + 1) % 3]), t);
+        }
+    }
+
+    let mut stack: Vec<(usize, usize)> = edges.keys().copied().collect();
+    while let Some((a, b)) = stack.pop() {
+        if constraints.contains(&_edge_key(a, b)) {
+            continue;
+        }
+        let (t1, t2) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+            (Some(&t1), Some(&t2)) => (t1, t2),
+            _ => continue,
+        };
+        let c = _opposite(&triangles[t1], a, b);
+        let d = _opposite(&triangles[t2], b, a);
+        if incircle(positions[a], positions[b], positions[c], positions[d]) <= C::zero() {
+            continue;
+        }
+        // Flip the diagonal of the quad a, d, b, c from a-b to c-d.
+        for &(p, q) in &[(a, b), (b, c), (c, a), (b, a), (a, d), (d, b)] {
+            edges.remove(&(p, q));
+        }
+        triangles[t1] = [a, d, c];
+        triangles[t2] = [d, b, c];
+        for &(t, triangle) in &[(t1, triangles[t1]), (t2, triangles[t2])] {
+            for k in 0..3 {
+                edges.insert((triangle[k], triangle[(k + 1) % 3]), t);
+            }
+        }
+        stack.extend_from_slice(&[(a, d), (d, b), (b, c), (c, a)]);
+    }
+
+    _to_triangles(&positions, &triangles)
+}
+
+fn _edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// The position of the triangle that isn't a or b.
+fn _opposite(triangle: &[usize; 3], a: usize, b: usize) -> usize {
+    *triangle.iter().find(|&&p| p != a && p != b).unwrap()
+}
+
+fn _to_triangles<C: Coordinate>(
+    positions: &[Position<C>],
+    triangles: &[[usize; 3]],
+) -> Vec<Triangle<C>> {
+    triangles
+        .iter()
+        .map(|t| Triangle::new(positions[t[0]], positions[t[1]], positions[t[2]]))
+        .collect()
+}
+
+/// The number of positions in a ring, not counting the closing one.
+fn _open_ring_len<C: Coordinate>(ring: &LineString<C>) -> usize {
+    let len = ring.num_points();
+    if len > 1 && ring.is_closed() {
+        len - 1
+    } else {
+        len
+    }
+}
+
+/// Twice the signed area of pqr: positive if counter-clockwise.
+fn _area<C: Coordinate>(p: Position<C>, q: Position<C>, r: Position<C>) -> C {
+    orient2d(p, q, r)
+}
+
+/// Check if p is inside or on the counter-clockwise triangle abc.
+fn _is_in_triangle<C: Coordinate>(
+    a: Position<C>,
+    b: Position<C>,
+    c: Position<C>,
+    p: Position<C>,
+) -> bool {
+    orient2d(p, a, b) >= C::zero()
+        && orient2d(p, b, c) >= C::zero()
+        && orient2d(p, c, a) >= C::zero()
+}
+
+/**
+ * Ear clip the polygon, returning all of its ring positions (exterior first,
+ * without closing positions) and the triangles as indices into them.
+ */
+fn _ear_clip<C: Coordinate>(polygon: &Polygon<C>) -> (Vec<Position<C>>, Vec<[usize; 3]>) {
+    let mut clipper = EarClipper {
+        positions: Vec::new(),
+        node_positions: Vec::new(),
+        prev: Vec::new(),
+        next: Vec::new(),
+        triangles: Vec::new(),
+    };
+    let outer = match clipper._add_ring(&polygon.exterior, true) {
+        Some(outer) => outer,
+        None => return (clipper.positions, clipper.triangles),
+    };
+
+    let mut holes: Vec<usize> = Vec::new();
+    for ring in &polygon.interiors {
+        if let Some(hole) = clipper._add_ring(ring, false) {
+            holes.push(clipper._find_leftmost(hole));
+        }
+    }
+    holes.sort_by(|&a, &b| {
+        let (pa, pb) = (clipper._position(a), clipper._position(b));
+        pa.partial_cmp(&pb).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let mut outer = outer;
+    for hole in holes {
+        outer = clipper._eliminate_hole(hole, outer);
+    }
+
+    clipper._clip_ears(outer, false);
+    (clipper.positions, clipper.triangles)
+}
+
+/// A circular doubly-linked list of nodes, each referring to a position.
+struct EarClipper<C: Coordinate> {
+    positions: Vec<Position<C>>,
+    node_positions: Vec<usize>,
+    prev: Vec<usize>,
+    next: Vec<usize>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl<C: Coordinate> EarClipper<C> {
+    fn _position(&self, node: usize) -> Position<C> {
+        self.positions[self.node_positions[node]]
+    }
+
+    fn _new_node(&mut self, position_index: usize) -> usize {
+        let node = self.node_positions.len();
+        self.node_positions.push(position_index);
+        self.prev.push(node);
+        self.next.push(node);
+        node
+    }
+
+    fn _remove_node(&mut self, node: usize) {
+        let (prev, next) = (self.prev[node], self.next[node]);
+        self.next[prev] = next;
+        self.prev[next] = prev;
+    }
+
+    /**
+     * Add the ring's positions, and link them counter-clockwise for the
+     * exterior or clockwise for holes.  Returns a node of the ring, or None
+     * if it is empty.
+     */
+    fn _add_ring(&mut self, ring: &LineString<C>, is_exterior: bool) -> Option<usize> {
+        let len = _open_ring_len(ring);
+        let positions = &ring.positions[..len];
+        let doubled_area: C = (0..len)
+            .map(|i| Position::cross(positions[i], positions[(i + 1) % len]))
+            .fold(C::zero(), |sum, a| sum + a);
+        let offset = self.positions.len();
+        self.positions.extend_from_slice(positions);
+
+        let mut order: Vec<usize> = (offset..offset + len).collect();
+        if (doubled_area > C::zero()) != is_exterior {
+            order.reverse();
+        }
+        let mut last: Option<usize> = None;
+        for position_index in order {
+            let node = self._new_node(position_index);
+            if let Some(last) = last {
+                self.next[node] = self.next[last];
+                self.prev[node] = last;
+                let after = self.next[last];
+                self.prev[after] = node;
+                self.next[last] = node;
+            }
+            last = Some(node);
+        }
+        let last = last?;
+        let next = self.next[last];
+        Some(self._filter_positions(next, None))
+    }
+
+    /**
+     * Remove repeated and collinear positions, from `start` until `end`.
+     * Returns a remaining node.
+     */
+    fn _filter_positions(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut node = start;
+        loop {
+            let (prev, next) = (self.prev[node], self.next[node]);
+            let p = self._position(node);
+            let is_redundant = p == self._position(next)
+                || _area(self._position(prev), p, self._position(next)) == C::zero();
+            if is_redundant && node != next {
+                self._remove_node(node);
+                node = prev;
+                end = prev;
+                if node == self.next[node] {
+                    break;
+                }
+            } else {
+                node = next;
+                if node == end {
+                    break;
+                }
+            }
+        }
+        end
+    }
+
+    fn _find_leftmost(&self, start: usize) -> usize {
+        let mut leftmost = start;
+        let mut node = self.next[start];
+        while node != start {
+            let (p, l) = (self._position(node), self._position(leftmost));
+            if p.x < l.x || (p.x == l.x && p.y < l.y) {
+                leftmost = node;
+            }
+            node = self.next[node];
+        }
+        leftmost
+    }
+
+    /// Bridge the hole to the outer ring, making them one ring.
+    fn _eliminate_hole(&mut self, hole: usize, outer: usize) -> usize {
+        let bridge = match self._find_hole_bridge(hole, outer) {
+            Some(bridge) => bridge,
+            None => return outer,
+        };
+        let bridge_reverse = self._split_ring(bridge, hole);
+        let next = self.next[bridge_reverse];
+        self._filter_positions(bridge_reverse, Some(next));
+        let next = self.next[bridge];
+        self._filter_positions(bridge, Some(next))
+    }
+
+    /**
+     * Find an outer node visible from the hole's leftmost node, by casting a
+     * ray to the left.
+     */
+    fn _find_hole_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let h = self._position(hole);
+        let mut qx = C::neg_infinity();
+        let mut candidate: Option<usize> = None;
+
+        // Find the nearest segment crossed by the ray; its endpoint with the
+        // lesser x is a potential bridge.
+        let mut node = outer;
+        loop {
+            let next = self.next[node];
+            let (p, n) = (self._position(node), self._position(next));
+            if h.y <= p.y && h.y >= n.y && n.y != p.y {
+                let x = p.x + (h.y - p.y) * (n.x - p.x) / (n.y - p.y);
+                if x <= h.x && x > qx {
+                    qx = x;
+                    candidate = Some(if p.x < n.x { node } else { next });
+                    if x == h.x {
+                        // The hole touches the segment.
+                        return candidate;
+                    }
+                }
+            }
+            node = next;
+            if node == outer {
+                break;
+            }
+        }
+        let mut bridge = candidate?;
+
+        // If other nodes are inside the triangle of the hole node, the ray
+        // crossing and the candidate, pick the one at the least angle to the ray.
+        let stop = bridge;
+        let m = self._position(bridge);
+        let mut min_tangent = C::infinity();
+        let mut node = bridge;
+        loop {
+            let p = self._position(node);
+            let (a, c) = if h.y < m.y {
+                (Position::new(h.x, h.y), Position::new(qx, h.y))
+            } else {
+                (Position::new(qx, h.y), Position::new(h.x, h.y))
+            };
+            if h.x >= p.x && p.x >= m.x && h.x != p.x && _is_in_triangle(a, m, c, p) {
+                let tangent = (h.y - p.y).abs() / (h.x - p.x);
+                let b = self._position(bridge);
+                if self._is_locally_inside(node, hole)
+                    && (tangent < min_tangent
+                        || (tangent == min_tangent
+                            && (p.x > b.x
+                                || (p.x == b.x && self._sector_contains_sector(bridge, node)))))
+                {
+                    bridge = node;
+                    min_tangent = tangent;
+                }
+            }
+            node = self.next[node];
+            if node == stop {
+                break;
+            }
+        }
+        Some(bridge)
+    }
+
+    /// Check if the diagonal from a to b starts inside the ring at a.
+    fn _is_locally_inside(&self, a: usize, b: usize) -> bool {
+        let (pa, pb) = (self._position(a), self._position(b));
+        let (prev, next) = (self._position(self.prev[a]), self._position(self.next[a]));
+        if _area(prev, pa, next) > C::zero() {
+            _area(pa, pb, next) <= C::zero() && _area(pa, prev, pb) <= C::zero()
+        } else {
+            _area(pa, pb, prev) > C::zero() || _area(pa, next, pb) > C::zero()
+        }
+    }
+
+    /// Check if the wedge at m contains the wedge at p (which have the same position).
+    fn _sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        let pm = self._position(m);
+        _area(
+            self._position(self.prev[m]),
+            pm,
+            self._position(self.prev[p]),
+        ) > C::zero()
+            && _area(
+                self._position(self.next[p]),
+                pm,
+                self._position(self.next[m]),
+            ) > C::zero()
+    }
+
+    /**
+     * Link a to b with a doubled diagonal, splitting the ring in two (or
+     * joining two rings into one).  Returns the copy of b.
+     */
+    fn _split_ring(&mut self, a: usize, b: usize) -> usize {
+        let a2 = self._new_node(self.node_positions[a]);
+        let b2 = self._new_node(self.node_positions[b]);
+        let (an, bp) = (self.next[a], self.prev[b]);
+        self.next[a] = b;
+        self.prev[b] = a;
+        self.next[a2] = an;
+        self.prev[an] = a2;
+        self.next[b2] = a2;
+        self.prev[a2] = b2;
+        self.next[bp] = b2;
+        self.prev[b2] = bp;
+        b2
+    }
+
+    fn _is_ear(&self, ear: usize) -> bool {
+        let (prev, next) = (self.prev[ear], self.next[ear]);
+        let (a, b, c) = (
+            self._position(prev),
+            self._position(ear),
+            self._position(next),
+        );
+        if _area(a, b, c) <= C::zero() {
+            // Reflex, so can't be an ear.
+            return false;
+        }
+        let mut node = self.next[next];
+        while node != prev {
+            let p = self._position(node);
+            if p != a
+                && _is_in_triangle(a, b, c, p)
+                && _area(
+                    self._position(self.prev[node]),
+                    p,
+                    self._position(self.next[node]),
+                ) <= C::zero()
+            {
+                return false;
+            }
+            node = self.next[node];
+        }
+        true
+    }
+
+    /**
+     * Clip ears until only a triangle's worth is left.  If no ear can be
+     * found, remove redundant positions and try again; if there are still no
+     * ears (which can only happen for invalid rings), clip a corner anyway.
+     */
+    fn _clip_ears(&mut self, start: usize, is_filtered: bool) {
+        let mut ear = start;
+        let mut stop = ear;
+        let mut is_filtered = is_filtered;
+        while self.prev[ear] != self.next[ear] {
+            let (prev, next) = (self.prev[ear], self.next[ear]);
+            if self._is_ear(ear) {
+                self.triangles.push([
+                    self.node_positions[prev],
+                    self.node_positions[ear],
+                    self.node_positions[next],
+                ]);
+                self._remove_node(ear);
+                ear = self.next[next];
+                stop = ear;
+                continue;
+            }
+            ear = next;
+            if ear == stop {
+                if !is_filtered {
+                    ear = self._filter_positions(ear, None);
+                    stop = ear;
+                    is_filtered = true;
+                } else {
+                    // Give up on finding a proper ear.
+                    let (prev, next) = (self.prev[ear], self.next[ear]);
+                    if _area(
+                        self._position(prev),
+                        self._position(ear),
+                        self._position(next),
+                    ) > C::zero()
+                    {
+                        self.triangles.push([
+                            self.node_positions[prev],
+                            self.node_positions[ear],
+                            self.node_positions[next],
+                        ]);
+                    }
+                    self._remove_node(ear);
+                    ear = next;
+                    stop = ear;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn total_area(triangles: &[Triangle<f64>]) -> f64 {
+        triangles.iter().map(|t| t.signed_area()).sum()
+    }
+
+    fn assert_covers(triangles: &[Triangle<f64>], area: f64) {
+        assert!(triangles.iter().all(|t| t.signed_area() > 0.));
+        assert!((total_area(triangles) - area).abs() < 1e-9);
+    }
+
+    #[test]
+    fn check_empty() {
+        let polygon: Polygon<f64> = Polygon::from(LineString::new(Vec::new()));
+        assert!(polygon.triangulate().is_empty());
+        assert!(polygon.triangulate_ear_clipping().is_empty());
+    }
+
+    #[test]
+    fn check_square_either_orientation() {
+        let square = vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)];
+        let mut reversed = square.clone();
+        reversed.reverse();
+        for ring in [square, reversed] {
+            let polygon = Polygon::from(ring);
+            assert_eq!(polygon.triangulate().len(), 2);
+            assert_covers(&polygon.triangulate(), 4.);
+            assert_covers(&polygon.triangulate_ear_clipping(), 4.);
+        }
+    }
+
+    #[test]
+    fn check_concave() {
+        let polygon = Polygon::from(vec![
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (8., 10.),
+            (8., 2.),
+            (2., 2.),
+            (2., 10.),
+            (0., 10.),
+            (0., 0.),
+        ]);
+        assert_covers(&polygon.triangulate_ear_clipping(), 52.);
+        assert_covers(&polygon.triangulate(), 52.);
+        assert_eq!(polygon.triangulate().len(), 6);
+    }
+
+    #[test]
+    fn check_holes() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(2., 2.), (4., 2.), (4., 4.), (2., 4.), (2., 2.)]),
+                LineString::from(vec![(6., 6.), (6., 8.), (8., 8.), (8., 6.), (6., 6.)]),
+            ],
+        );
+        let area = 100. - 4. - 4.;
+        assert_covers(&polygon.triangulate_ear_clipping(), area);
+        let triangles = polygon.triangulate();
+        assert_covers(&triangles, area);
+        // n positions with h holes give n + 2h - 2 triangles.
+        assert_eq!(triangles.len(), 12 + 4 - 2);
+    }
+
+    #[test]
+    fn check_delaunay_avoids_slivers() {
+        // A fan of positions along an arc; ear clipping makes thin triangles
+        // from the first corner, the constrained Delaunay ones are fatter.
+        let mut coords: Vec<(f64, f64)> = (0..=8)
+            .map(|i| {
+                let angle = std::f64::consts::PI * i as f64 / 8.;
+                (angle.cos() * 10., angle.sin())
+            })
+            .collect();
+        coords.push(coords[0]);
+        let polygon = Polygon::from(coords);
+        let triangles = polygon.triangulate();
+        let area = total_area(&polygon.triangulate_ear_clipping());
+        assert_covers(&triangles, area);
+        let positions: Vec<Position<f64>> = triangles
+            .iter()
+            .flat_map(|t| t.to_array().to_vec())
+            .collect();
+        for t in &triangles {
+            for &p in &positions {
+                assert!(incircle(t.0, t.1, t.2, p) <= 0.);
+            }
+        }
+    }
+}
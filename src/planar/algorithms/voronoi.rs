@@ -0,0 +1,175 @@
+use super::delaunay::{next_halfedge, DelaunayTriangulation};
+use crate::planar::primitives::{Envelope, Position, SafePosition};
+use crate::planar::types::{LineString, Polygon};
+use crate::Coordinate;
+use std::collections::{BTreeSet, HashMap};
+
+/**
+ * Find the Voronoi cells of the positions, clipped to the envelope.
+ *
+ * The cells are returned in the same order as the positions.  Each cell is
+ * the envelope, clipped by the half-plane nearer the position than each of
+ * its Delaunay neighbors (the only positions that can bound its cell).  The
+ * cells are convex, with counter-clockwise exteriors.
+ *
+ * Duplicate positions get identical cells.  Positions with NaN coordinates,
+ * and positions whose cell doesn't reach into the envelope, get empty cells.
+ */
+pub fn find_voronoi_cells<C: Coordinate>(
+    positions: &[Position<C>],
+    envelope: Envelope<C>,
+) -> Vec<Polygon<C>> {
+    let (min, max) = match envelope {
+        Envelope::Empty => return positions.iter().map(|_| _empty_cell()).collect(),
+        Envelope::Bounds { min, max } => (min, max),
+    };
+    let frame = vec![
+        min,
+        Position::new(max.x, min.y),
+        max,
+        Position::new(min.x, max.y),
+    ];
+
+    let triangulation = DelaunayTriangulation::new(positions);
+    let mut neighbors: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); positions.len()];
+    let triangles = &triangulation.triangles;
+    for e in 0..triangles.len() {
+        let (a, b) = (triangles[e], triangles[next_halfedge(e)]);
+        neighbors[a].insert(b);
+        neighbors[b].insert(a);
+    }
+    if triangulation.num_triangles() == 0 {
+        // Collinear positions: the neighbors are adjacent along the line.
+        for pair in triangulation.hull.windows(2) {
+            neighbors[pair[0]].insert(pair[1]);
+            neighbors[pair[1]].insert(pair[0]);
+        }
+    }
+
+    // The triangulation skips duplicates, so they share the included one's cell.
+    let mut sites: HashMap<SafePosition<C>, usize> = HashMap::new();
+    for &i in triangles.iter().chain(triangulation.hull.iter()) {
+        if let Ok(key) = positions[i].to_hashable() {
+            sites.insert(key, i);
+        }
+    }
+
+    positions
+        .iter()
+        .map(|position| {
+            let site = match position.to_hashable().ok().and_then(|k| sites.get(&k)) {
+                Some(&site) => site,
+                None => return _empty_cell(),
+            };
+            let mut cell = frame.clone();
+            for &neighbor in &neighbors[site] {
+                cell = _clip_to_half_plane(&cell, positions[site], positions[neighbor]);
+            }
+            if cell.len() < 3 {
+                return _empty_cell();
+            }
+            cell.push(cell[0]);
+            Polygon::from(LineString::new(cell))
+        })
+        .collect()
+}
+
+fn _empty_cell<C: Coordinate>() -> Polygon<C> {
+    Polygon::from(LineString::new(Vec::new()))
+}
+
+/**
+ * Clip a convex polygon (as an open ring) to the half-plane of positions at
+ * least as near `site` as `other`.
+ */
+fn _clip_to_half_plane<C: Coordinate>(
+    ring: &[Position<C>],
+    site: Position<C>,
+    other: Position<C>,
+) -> Vec<Position<C>> {
+    let middle = (site + other) / (C::one() + C::one());
+    let normal = other - site;
+    // Positive beyond the bisector, on the other's side.
+    let side = |p: Position<C>| Position::dot(p - middle, normal);
+
+    let mut clipped = Vec::with_capacity(ring.len() + 1);
+    for (index, &current) in ring.iter().enumerate() {
+        let next = ring[(index + 1) % ring.len()];
+        let (current_side, next_side) = (side(current), side(next));
+        if current_side <= C::zero() {
+            clipped.push(current);
+        }
+        if (current_side < C::zero() && next_side > C::zero())
+            || (current_side > C::zero() && next_side < C::zero())
+        {
+            let t = current_side / (current_side - next_side);
+            clipped.push(current + (next - current) * t);
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::properties::Area;
+    use crate::planar::types::MultiPoint;
+
+    fn square_envelope() -> Envelope<f64> {
+        Envelope::new(Position::new(0., 0.), Position::new(4., 4.))
+    }
+
+    #[test]
+    fn check_single_point() {
+        let mp = MultiPoint::from(vec![(1., 1.)]);
+        let cells = mp.voronoi_diagram(square_envelope());
+        assert_eq!(
+            cells,
+            vec![Polygon::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (4., 4.),
+                (0., 4.),
+                (0., 0.)
+            ])]
+        );
+    }
+
+    #[test]
+    fn check_two_points() {
+        let mp = MultiPoint::from(vec![(3., 2.), (1., 2.)]);
+        let cells = mp.voronoi_diagram(square_envelope());
+        assert_eq!(
+            cells[0],
+            Polygon::from(vec![(2., 0.), (4., 0.), (4., 4.), (2., 4.), (2., 0.)])
+        );
+        assert_eq!(
+            cells[1],
+            Polygon::from(vec![(0., 0.), (2., 0.), (2., 4.), (0., 4.), (0., 0.)])
+        );
+    }
+
+    #[test]
+    fn check_grid_cells() {
+        let mp = MultiPoint::from(vec![(1., 1.), (3., 1.), (1., 3.), (3., 3.), (2., 2.)]);
+        let cells = mp.voronoi_diagram(square_envelope());
+        assert_eq!(cells.len(), 5);
+        // The center cell is a diamond, and the cells tile the envelope.
+        assert_eq!(cells[4].exterior.num_points(), 5);
+        assert_eq!(cells[4].area(), 2.);
+        assert_eq!(cells.iter().map(Area::area).sum::<f64>(), 16.);
+    }
+
+    #[test]
+    fn check_duplicates_and_outside() {
+        let mp = MultiPoint::from(vec![(1., 2.), (3., 2.), (1., 2.), (100., 2.)]);
+        let cells = mp.voronoi_diagram(square_envelope());
+        assert_eq!(cells[0], cells[2]);
+        assert!(cells[3].is_empty());
+        assert!(mp
+            .voronoi_diagram(Envelope::empty())
+            .iter()
+            .all(|c| c.is_empty()));
+    }
+}
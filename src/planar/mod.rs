@@ -1,5 +1,6 @@
+pub mod algorithms;
 pub mod primitives;
+pub(crate) mod properties;
 pub mod types;
 
-mod algorithms;
-mod validation;
+pub mod validation;
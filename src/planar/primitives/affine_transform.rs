@@ -0,0 +1,185 @@
+use crate::planar::primitives::Position;
+use crate::Coordinate;
+
+/**
+ * An affine transformation of the plane.
+ *
+ * A position (x, y) is mapped to (a * x + b * y + x_offset,
+ * d * x + e * y + y_offset).  Angles are in radians, counter-clockwise.
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AffineTransform<C: Coordinate> {
+    pub a: C,
+    pub b: C,
+    pub x_offset: C,
+    pub d: C,
+    pub e: C,
+    pub y_offset: C,
+}
+
+impl<C: Coordinate> Default for AffineTransform<C> {
+    fn default() -> Self {
+        AffineTransform::identity()
+    }
+}
+
+impl<C: Coordinate> AffineTransform<C> {
+    pub fn new(a: C, b: C, x_offset: C, d: C, e: C, y_offset: C) -> Self {
+        AffineTransform {
+            a,
+            b,
+            x_offset,
+            d,
+            e,
+            y_offset,
+        }
+    }
+
+    pub fn identity() -> Self {
+        let (zero, one) = (C::zero(), C::one());
+        AffineTransform::new(one, zero, zero, zero, one, zero)
+    }
+
+    pub fn translate(dx: C, dy: C) -> Self {
+        let (zero, one) = (C::zero(), C::one());
+        AffineTransform::new(one, zero, dx, zero, one, dy)
+    }
+
+    /// Scale by `sx` and `sy`, keeping `origin` fixed.
+    pub fn scale(sx: C, sy: C, origin: Position<C>) -> Self {
+        let zero = C::zero();
+        AffineTransform::new(sx, zero, zero, zero, sy, zero)._about(origin)
+    }
+
+    /// Rotate counter-clockwise by `angle`, about `origin`.
+    pub fn rotate(angle: C, origin: Position<C>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        AffineTransform::new(cos, -sin, C::zero(), sin, cos, C::zero())._about(origin)
+    }
+
+    /**
+     * Skew by `x_angle` along the x axis, and `y_angle` along the y axis,
+     * keeping `origin` fixed.  A skew by `x_angle` tilts vertical lines by
+     * that angle.
+     */
+    pub fn skew(x_angle: C, y_angle: C, origin: Position<C>) -> Self {
+        let (zero, one) = (C::zero(), C::one());
+        AffineTransform::new(one, x_angle.tan(), zero, y_angle.tan(), one, zero)._about(origin)
+    }
+
+    /// Conjugate a linear transformation so it keeps `origin` fixed.
+    fn _about(self, origin: Position<C>) -> Self {
+        AffineTransform::translate(-origin.x, -origin.y)
+            .compose(&self)
+            .compose(&AffineTransform::translate(origin.x, origin.y))
+    }
+
+    /// The transformation that applies `self`, and then `other`.
+    pub fn compose(&self, other: &AffineTransform<C>) -> Self {
+        AffineTransform::new(
+            other.a * self.a + other.b * self.d,
+            other.a * self.b + other.b * self.e,
+            other.a * self.x_offset + other.b * self.y_offset + other.x_offset,
+            other.d * self.a + other.e * self.d,
+            other.d * self.b + other.e * self.e,
+            other.d * self.x_offset + other.e * self.y_offset + other.y_offset,
+        )
+    }
+
+    /// The inverse transformation, or None if this one collapses the plane.
+    pub fn invert(&self) -> Option<Self> {
+        let determinant = self.a * self.e - self.b * self.d;
+        if determinant == C::zero() || !determinant.is_finite() {
+            return None;
+        }
+        let (a, b) = (self.e / determinant, -self.b / determinant);
+        let (d, e) = (-self.d / determinant, self.a / determinant);
+        Some(AffineTransform::new(
+            a,
+            b,
+            -(a * self.x_offset + b * self.y_offset),
+            d,
+            e,
+            -(d * self.x_offset + e * self.y_offset),
+        ))
+    }
+
+    pub fn apply(&self, position: Position<C>) -> Position<C> {
+        Position::new(
+            self.a * position.x + self.b * position.y + self.x_offset,
+            self.d * position.x + self.e * position.y + self.y_offset,
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::primitives::Envelope;
+    use crate::planar::types::{Geometry, Polygon};
+
+    fn assert_near(p: Position<f64>, q: Position<f64>) {
+        assert!(
+            (p.x - q.x).abs() < 1e-12 && (p.y - q.y).abs() < 1e-12,
+            "{:?} != {:?}",
+            p,
+            q
+        );
+    }
+
+    #[test]
+    fn check_translate_and_scale() {
+        let p = Position::new(1., 2.);
+        assert_eq!(
+            AffineTransform::translate(3., -1.).apply(p),
+            Position::new(4., 1.)
+        );
+        let scale = AffineTransform::scale(2., 3., Position::new(1., 1.));
+        assert_eq!(scale.apply(p), Position::new(1., 4.));
+    }
+
+    #[test]
+    fn check_rotate_about_point() {
+        let rotate = AffineTransform::rotate(std::f64::consts::FRAC_PI_2, Position::new(1., 1.));
+        assert_near(rotate.apply(Position::new(2., 1.)), Position::new(1., 2.));
+        assert_near(rotate.apply(Position::new(1., 1.)), Position::new(1., 1.));
+    }
+
+    #[test]
+    fn check_skew() {
+        let skew = AffineTransform::skew(std::f64::consts::FRAC_PI_4, 0., Position::new(0., 0.));
+        assert_near(skew.apply(Position::new(0., 1.)), Position::new(1., 1.));
+    }
+
+    #[test]
+    fn check_compose_order() {
+        let translate = AffineTransform::translate(1., 0.);
+        let scale = AffineTransform::scale(2., 2., Position::new(0., 0.));
+        let p = Position::new(1., 1.);
+        assert_eq!(translate.compose(&scale).apply(p), Position::new(4., 2.));
+        assert_eq!(scale.compose(&translate).apply(p), Position::new(3., 2.));
+    }
+
+    #[test]
+    fn check_invert() {
+        let transform = AffineTransform::rotate(0.3, Position::new(5., -2.))
+            .compose(&AffineTransform::scale(2., 0.5, Position::new(1., 1.)))
+            .compose(&AffineTransform::translate(7., 3.));
+        let inverse = transform.invert().unwrap();
+        let p = Position::new(-3., 4.);
+        assert_near(inverse.apply(transform.apply(p)), p);
+        assert_eq!(AffineTransform::scale(0., 1., p).invert(), None);
+    }
+
+    #[test]
+    fn check_transform_geometry_envelope() {
+        let polygon = Polygon::from(vec![(0., 0.), (2., 0.), (2., 1.), (0., 1.), (0., 0.)]);
+        let transform = AffineTransform::translate(10., 20.);
+        let moved = Geometry::from(polygon).transform(&transform);
+        assert_eq!(
+            moved.envelope(),
+            Envelope::new(Position::new(10., 20.), Position::new(12., 21.))
+        );
+    }
+}
@@ -1,11 +1,17 @@
+mod affine_transform;
 mod envelope;
 mod position;
+mod precision_model;
+mod predicates;
 mod segment;
 mod triangle;
 
 pub use crate::planar::primitives::{
+    affine_transform::AffineTransform,
     envelope::{Envelope, HasEnvelope},
     position::{Position, SafePosition},
+    precision_model::{PrecisionModel, Scale},
+    predicates::{incircle, orient2d},
     segment::{PositionLocation, Segment, SegmentIntersection},
     triangle::Triangle,
 };
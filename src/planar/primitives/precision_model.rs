@@ -0,0 +1,162 @@
+use crate::planar::primitives::Position;
+use crate::Coordinate;
+
+/**
+ * How precisely coordinates are represented.
+ *
+ * Computed positions, like the crossing of two segments, are rounded with
+ * the model, so that results are on the same grid as the inputs and don't
+ * depend on the order of the computation.  The default is `Floating`.
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PrecisionModel<C: Coordinate> {
+    /// The full precision of the coordinate type.
+    Floating,
+    /// The precision of an f32, even if coordinates are stored as f64.
+    FloatingSingle,
+    /// Coordinates are whole multiples of 1 / scale; eg a scale of 1000 keeps
+    /// three decimal places.
+    Fixed(Scale<C>),
+}
+
+/**
+ * The scale of a fixed precision model, which is always positive and finite.
+ * It can only be made with `Scale::new`, so every fixed model has a grid.
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Scale<C: Coordinate>(C);
+
+impl<C: Coordinate> Scale<C> {
+    /// The scale, or None if it isn't positive and finite.
+    pub fn new(scale: C) -> Option<Self> {
+        if scale.is_finite() && scale > C::zero() {
+            Some(Scale(scale))
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self) -> C {
+        self.0
+    }
+}
+
+// Deriving Default for an enum needs a `#[default]` variant, from Rust 1.62.
+#[allow(clippy::derivable_impls)]
+impl<C: Coordinate> Default for PrecisionModel<C> {
+    fn default() -> Self {
+        PrecisionModel::Floating
+    }
+}
+
+impl<C: Coordinate> PrecisionModel<C> {
+    /**
+     * A fixed model, with coordinates on a grid of size 1 / scale, or None if
+     * the scale isn't positive and finite.
+     */
+    pub fn fixed(scale: C) -> Option<Self> {
+        Scale::new(scale).map(PrecisionModel::Fixed)
+    }
+
+    /**
+     * A fixed model keeping a number of decimal places, or None if that is
+     * too many (or too few) for the coordinate type.
+     */
+    pub fn decimal_places(places: i32) -> Option<Self> {
+        PrecisionModel::fixed(C::from(10).unwrap().powi(places))
+    }
+
+    /// The distance between grid lines, or None if the model isn't fixed.
+    pub fn grid_size(&self) -> Option<C> {
+        match self {
+            PrecisionModel::Fixed(scale) => Some(C::one() / scale.value()),
+            _ => None,
+        }
+    }
+
+    pub fn is_floating(&self) -> bool {
+        !matches!(self, PrecisionModel::Fixed(_))
+    }
+
+    /**
+     * Round a coordinate to the model.  A fixed model rounds to the nearest
+     * grid line, with halves away from zero.  Rounding is idempotent, and
+     * non-finite values are unchanged, as are values too large to scale.
+     */
+    pub fn make_precise(&self, value: C) -> C {
+        match self {
+            PrecisionModel::Floating => value,
+            PrecisionModel::FloatingSingle => match value.to_f32() {
+                Some(single) => C::from(single).unwrap(),
+                None => value,
+            },
+            PrecisionModel::Fixed(scale) => {
+                let scaled = value * scale.value();
+                if scaled.is_finite() {
+                    scaled.round() / scale.value()
+                } else {
+                    value
+                }
+            }
+        }
+    }
+
+    /// Round both coordinates of a position to the model.
+    pub fn make_precise_position(&self, position: Position<C>) -> Position<C> {
+        Position::new(self.make_precise(position.x), self.make_precise(position.y))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_floating() {
+        let model = PrecisionModel::default();
+        assert!(model.is_floating());
+        assert_eq!(model.grid_size(), None);
+        assert_eq!(model.make_precise(0.1), 0.1);
+    }
+
+    #[test]
+    fn check_floating_single() {
+        let model = PrecisionModel::FloatingSingle;
+        assert_eq!(model.make_precise(0.1f64), 0.1f32 as f64);
+        assert_eq!(model.make_precise(0.5f64), 0.5);
+    }
+
+    #[test]
+    fn check_fixed() {
+        let model = PrecisionModel::fixed(4.).unwrap();
+        assert!(!model.is_floating());
+        assert_eq!(model.grid_size(), Some(0.25));
+        assert_eq!(model.make_precise(1.3), 1.25);
+        assert_eq!(model.make_precise(-1.375), -1.5);
+        assert_eq!(model.make_precise(f64::INFINITY), f64::INFINITY);
+        assert!(model.make_precise(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn check_invalid_scales() {
+        assert_eq!(PrecisionModel::fixed(0.), None);
+        assert_eq!(PrecisionModel::fixed(-1.), None);
+        assert_eq!(PrecisionModel::fixed(f64::NAN), None);
+        assert_eq!(PrecisionModel::fixed(f64::INFINITY), None);
+        assert_eq!(PrecisionModel::<f32>::decimal_places(40), None);
+        assert_eq!(PrecisionModel::<f32>::decimal_places(-40), None);
+        // Values too large to scale are already on the grid.
+        let model = PrecisionModel::fixed(1e300).unwrap();
+        assert_eq!(model.make_precise(1e10), 1e10);
+    }
+
+    #[test]
+    fn check_decimal_places() {
+        let model = PrecisionModel::decimal_places(7).unwrap();
+        let position = Position::new(12.345678949, -0.000000051);
+        let precise = model.make_precise_position(position);
+        assert_eq!(precise, Position::new(12.3456789, -0.0000001));
+        assert_eq!(model.make_precise_position(precise), precise);
+    }
+}
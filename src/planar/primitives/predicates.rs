@@ -0,0 +1,293 @@
+use crate::planar::primitives::Position;
+use crate::Coordinate;
+
+/**
+ * Check which way a, b, c turn.
+ *
+ * Returns a positive value if they are in counterclockwise order, negative
+ * if clockwise, and zero if they are collinear.  The sign is always exact.
+ * This is Shewchuk's adaptive predicate: the determinant is first computed
+ * with floats, and only if it is within the possible rounding error of zero
+ * is it recomputed exactly.  The value is approximately twice the signed area
+ * of the triangle abc.
+ */
+pub fn orient2d<C: Coordinate>(a: Position<C>, b: Position<C>, c: Position<C>) -> C {
+    let det_left = (a.x - c.x) * (b.y - c.y);
+    let det_right = (a.y - c.y) * (b.x - c.x);
+    let det = det_left - det_right;
+
+    // If the terms have opposite signs, there can't be cancellation.
+    let det_sum = if det_left > C::zero() {
+        if det_right <= C::zero() {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < C::zero() {
+        if det_right >= C::zero() {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let epsilon = _epsilon::<C>();
+    let three = C::from(3).unwrap();
+    let error_bound = (three + C::from(16).unwrap() * epsilon) * epsilon * det_sum;
+    if det.abs() >= error_bound {
+        return det;
+    }
+    _orient2d_exact(a, b, c)
+}
+
+/**
+ * Check if d is inside the circumcircle of counterclockwise a, b, c.
+ *
+ * Returns a positive value if d is strictly inside, negative if strictly
+ * outside, and zero if the positions are cocircular.  If a, b, c are in
+ * clockwise order, the sign is reversed.  Like `orient2d`, the sign is exact,
+ * and the exact computation is only done when the float one can't be trusted.
+ */
+pub fn incircle<C: Coordinate>(
+    a: Position<C>,
+    b: Position<C>,
+    c: Position<C>,
+    d: Position<C>,
+) -> C {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (al, bl, cl) = (
+        Position::dot(ad, ad),
+        Position::dot(bd, bd),
+        Position::dot(cd, cd),
+    );
+    let det =
+        al * Position::cross(bd, cd) + bl * Position::cross(cd, ad) + cl * Position::cross(ad, bd);
+
+    let permanent = al * ((bd.x * cd.y).abs() + (cd.x * bd.y).abs())
+        + bl * ((cd.x * ad.y).abs() + (ad.x * cd.y).abs())
+        + cl * ((ad.x * bd.y).abs() + (bd.x * ad.y).abs());
+    let epsilon = _epsilon::<C>();
+    let error_bound = (C::from(10).unwrap() + C::from(96).unwrap() * epsilon) * epsilon * permanent;
+    if det.abs() > error_bound {
+        return det;
+    }
+    _incircle_exact(a, b, c, d)
+}
+
+fn _orient2d_exact<C: Coordinate>(a: Position<C>, b: Position<C>, c: Position<C>) -> C {
+    let (acx, acy) = (_two_diff(a.x, c.x), _two_diff(a.y, c.y));
+    let (bcx, bcy) = (_two_diff(b.x, c.x), _two_diff(b.y, c.y));
+    let det = _expansion_diff(
+        &_expansion_product(&acx, &bcy),
+        &_expansion_product(&acy, &bcx),
+    );
+    _estimate(&det)
+}
+
+fn _incircle_exact<C: Coordinate>(
+    a: Position<C>,
+    b: Position<C>,
+    c: Position<C>,
+    d: Position<C>,
+) -> C {
+    let (adx, ady) = (_two_diff(a.x, d.x), _two_diff(a.y, d.y));
+    let (bdx, bdy) = (_two_diff(b.x, d.x), _two_diff(b.y, d.y));
+    let (cdx, cdy) = (_two_diff(c.x, d.x), _two_diff(c.y, d.y));
+    let cross = |ux: &[C], uy: &[C], vx: &[C], vy: &[C]| {
+        _expansion_diff(&_expansion_product(ux, vy), &_expansion_product(uy, vx))
+    };
+    let lift =
+        |x: &[C], y: &[C]| _expansion_sum(&_expansion_product(x, x), &_expansion_product(y, y));
+
+    let a_term = _expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let b_term = _expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let c_term = _expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+    _estimate(&_expansion_sum(&_expansion_sum(&a_term, &b_term), &c_term))
+}
+
+// Exact arithmetic on expansions: sums of floats, in increasing order of
+// magnitude, whose significant bits don't overlap.  See Shewchuk, "Adaptive
+// Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+
+/// Half the distance from 1 to the next float, which bounds relative rounding error.
+fn _epsilon<C: Coordinate>() -> C {
+    C::epsilon() / (C::one() + C::one())
+}
+
+/// 2^ceil(p / 2) + 1, for p bits of mantissa, to split floats into halves.
+fn _splitter<C: Coordinate>() -> C {
+    let bits = (-C::epsilon().log2()).round().to_i32().unwrap() + 1;
+    (C::one() + C::one()).powi((bits + 1) / 2) + C::one()
+}
+
+/// a + b as the rounded sum and its rounding error.
+fn _two_sum<C: Coordinate>(a: C, b: C) -> (C, C) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// a + b, for |a| >= |b|.
+fn _fast_two_sum<C: Coordinate>(a: C, b: C) -> (C, C) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+/// a - b as an expansion.
+fn _two_diff<C: Coordinate>(a: C, b: C) -> [C; 2] {
+    let x = a - b;
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    [(a - a_virtual) + (b_virtual - b), x]
+}
+
+fn _split<C: Coordinate>(a: C) -> (C, C) {
+    let c = _splitter::<C>() * a;
+    let high = c - (c - a);
+    (high, a - high)
+}
+
+/// a * b as the rounded product and its rounding error.
+fn _two_product<C: Coordinate>(a: C, b: C) -> (C, C) {
+    let x = a * b;
+    let (a_high, a_low) = _split(a);
+    let (b_high, b_low) = _split(b);
+    let error = ((x - a_high * b_high) - a_low * b_high) - a_high * b_low;
+    (x, a_low * b_low - error)
+}
+
+/// e + b, dropping zero components.
+fn _grow_expansion<C: Coordinate>(e: &[C], b: C) -> Vec<C> {
+    let mut h = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = _two_sum(q, component);
+        if error != C::zero() {
+            h.push(error);
+        }
+        q = sum;
+    }
+    if q != C::zero() || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+fn _expansion_sum<C: Coordinate>(e: &[C], f: &[C]) -> Vec<C> {
+    f.iter().fold(e.to_vec(), |sum, &component| {
+        _grow_expansion(&sum, component)
+    })
+}
+
+fn _expansion_diff<C: Coordinate>(e: &[C], f: &[C]) -> Vec<C> {
+    let negated: Vec<C> = f.iter().map(|&component| -component).collect();
+    _expansion_sum(e, &negated)
+}
+
+/// e * b, dropping zero components.
+fn _scale_expansion<C: Coordinate>(e: &[C], b: C) -> Vec<C> {
+    let mut h = Vec::with_capacity(2 * e.len());
+    let (mut q, error) = _two_product(e[0], b);
+    if error != C::zero() {
+        h.push(error);
+    }
+    for &component in &e[1..] {
+        let (product, product_error) = _two_product(component, b);
+        let (sum, error) = _two_sum(q, product_error);
+        if error != C::zero() {
+            h.push(error);
+        }
+        let (new_q, error) = _fast_two_sum(product, sum);
+        if error != C::zero() {
+            h.push(error);
+        }
+        q = new_q;
+    }
+    if q != C::zero() || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+fn _expansion_product<C: Coordinate>(e: &[C], f: &[C]) -> Vec<C> {
+    f.iter().fold(vec![C::zero()], |sum, &component| {
+        _expansion_sum(&sum, &_scale_expansion(e, component))
+    })
+}
+
+/// The value of an expansion, rounded.  Its sign is the sign of the expansion.
+fn _estimate<C: Coordinate>(e: &[C]) -> C {
+    e.iter().fold(C::zero(), |sum, &component| sum + component)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_orient2d() {
+        let (a, b) = (Position::new(0., 0.), Position::new(1., 1.));
+        assert!(orient2d(a, b, Position::new(0., 1.)) > 0.);
+        assert!(orient2d(a, b, Position::new(1., 0.)) < 0.);
+        assert!(orient2d(a, b, Position::new(2., 2.)) == 0.);
+    }
+
+    #[test]
+    fn check_orient2d_near_collinear() {
+        // Positions a few ulps off the line y = x, where the float
+        // determinant gets the sign wrong.  This is the test from Shewchuk's
+        // paper.
+        let a = Position::new(12., 12.);
+        let b = Position::new(24., 24.);
+        for i in 0..64 {
+            let x = 0.5 + f64::EPSILON * i as f64;
+            for j in 0..64 {
+                let y = 0.5 + f64::EPSILON * j as f64;
+                let result = orient2d(Position::new(x, y), a, b);
+                if y > x {
+                    assert!(result > 0.);
+                } else if y < x {
+                    assert!(result < 0.);
+                } else {
+                    assert!(result == 0.);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_orient2d_f32() {
+        let a = Position::new(12f32, 12.);
+        let b = Position::new(24f32, 24.);
+        let c = Position::new(0.5f32 + f32::EPSILON, 0.5);
+        assert!(orient2d(c, a, b) < 0.);
+        assert!(orient2d(Position::new(0.5f32, 0.5), a, b) == 0.);
+    }
+
+    #[test]
+    fn check_incircle() {
+        let a = Position::new(0., 0.);
+        let b = Position::new(1., 0.);
+        let c = Position::new(0., 1.);
+        assert!(incircle(a, b, c, Position::new(0.5, 0.5)) > 0.);
+        assert!(incircle(a, b, c, Position::new(2., 2.)) < 0.);
+        assert!(incircle(a, b, c, Position::new(1., 1.)) == 0.);
+    }
+
+    #[test]
+    fn check_incircle_near_cocircular() {
+        // Positions on the unit circle, and ones an ulp inside and outside.
+        let a = Position::new(1., 0.);
+        let b = Position::new(0., 1.);
+        let c = Position::new(-1., 0.);
+        assert!(incircle(a, b, c, Position::new(0., -1.)) == 0.);
+        let inside = Position::new(0., -1. + f64::EPSILON);
+        assert!(incircle(a, b, c, inside) > 0.);
+        let outside = Position::new(0., -1. - f64::EPSILON);
+        assert!(incircle(a, b, c, outside) < 0.);
+        // Shifting far from the origin makes the float determinant unreliable.
+        let offset = Position::new(1e9, 1e9);
+        assert!(incircle(a + offset, b + offset, c + offset, inside + offset) >= 0.);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::planar::primitives::{Envelope, HasEnvelope, Position};
+use crate::planar::primitives::{orient2d, Envelope, HasEnvelope, Position};
 use crate::Coordinate;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -61,10 +61,31 @@ impl<C: Coordinate> Segment<C> {
         self.length_squared().sqrt()
     }
 
+    /**
+     * Find the position on the segment closest to `position`.
+     *
+     * If the segment is degenerate (start == end), this is the start.
+     */
+    pub fn closest_position(&self, position: Position<C>) -> Position<C> {
+        let length_squared = self.length_squared();
+        if length_squared == C::zero() {
+            return self.start;
+        }
+        let direction = self.end - self.start;
+        let t = Position::dot(position - self.start, direction) / length_squared;
+        self.start + direction * t.max(C::zero()).min(C::one())
+    }
+
+    /// Euclidean distance from `position` to the closest point on the segment.
+    pub fn distance_to_position(&self, position: Position<C>) -> C {
+        Segment::new(self.closest_position(position), position).length()
+    }
+
     /// Tests if a positions is Left|On|Right of the infinite line determined by the segment.
     ///    Return: PositionLocation for location of p relative to [start, end]
+    /// The test is exact, using `orient2d`.
     pub fn position_location(&self, position: Position<C>) -> PositionLocation {
-        let test = Position::cross(self.end - self.start, position - self.start);
+        let test = orient2d(self.start, self.end, position);
         if test > C::zero() {
             PositionLocation::Left
         } else if test == C::zero() {
@@ -86,6 +107,11 @@ impl<C: Coordinate> Segment<C> {
     /**
      * Check the intersection of two segments.
      *
+     * Whether they intersect, and whether they are collinear, is decided
+     * exactly with `orient2d`.  If an endpoint of one segment is on the
+     * other, it is returned exactly.  Otherwise a crossing is computed, and
+     * may be rounded.
+     *
      * NB: This does not do an initial check with Envelopes; the caller should do that.
      */
     pub fn intersect_segment(&self, other: Segment<C>) -> SegmentIntersection<C> {
@@ -94,51 +120,87 @@ impl<C: Coordinate> Segment<C> {
             return SegmentIntersection::Segment(*self);
         }
 
+        let other_start = self.position_location(other.start);
+        let other_end = self.position_location(other.end);
+        if other_start == PositionLocation::On && other_end == PositionLocation::On {
+            return self._intersect_collinear(other);
+        }
+        if other_start == other_end {
+            // The other segment is strictly on one side of this one.
+            return SegmentIntersection::None;
+        }
+        let self_start = other.position_location(self.start);
+        let self_end = other.position_location(self.end);
+        if self_start == self_end {
+            return SegmentIntersection::None;
+        }
+
+        // They intersect at one position; if it is an endpoint, use it exactly.
+        if other_start == PositionLocation::On {
+            return SegmentIntersection::Position(other.start);
+        } else if other_end == PositionLocation::On {
+            return SegmentIntersection::Position(other.end);
+        } else if self_start == PositionLocation::On {
+            return SegmentIntersection::Position(self.start);
+        } else if self_end == PositionLocation::On {
+            return SegmentIntersection::Position(self.end);
+        }
+
+        // The segments properly cross.  Calculate where the infinite lines
+        // intersect, keeping it on the segment despite rounding.
+        let da = self.end - self.start;
+        let db = other.end - other.start;
+        let offset = other.start - self.start;
+        let ta = Position::cross(offset, db) / Position::cross(da, db);
+        let ta = if ta.is_finite() {
+            ta.max(C::zero()).min(C::one())
+        } else {
+            // The rounded cross product is zero for nearly parallel segments.
+            C::one() / (C::one() + C::one())
+        };
+        SegmentIntersection::Position(self.start + da * ta)
+    }
+
+    /// The intersection of two segments on the same line.
+    fn _intersect_collinear(&self, other: Segment<C>) -> SegmentIntersection<C> {
         let da = self.end - self.start; // The vector for the segment
         let db = other.end - other.start; // The vector for the other segment
         let offset = other.start - self.start; // The offset between segments (starts)
-
-        let da_x_db = Position::cross(da, db);
-        let offset_x_da = Position::cross(offset, da);
-
-        if da_x_db == C::zero() {
-            // This means the two segments are parallel.
-            // If the offset is not also parallel, they must be disjoint.
-            if offset_x_da != C::zero() {
-                return SegmentIntersection::None;
+        let da_2 = Position::dot(da, da);
+        if da_2 == C::zero() {
+            // This segment is a single position, on the other.
+            return if other.contains(self.start) {
+                SegmentIntersection::Position(self.start)
             } else {
-                // If the offset is also parallel, check for overlap.
-                let da_2 = Position::dot(da, da);
-                // Offset, in units of da.
-                let t0 = Position::dot(offset, da) / da_2;
-                // self.start to other.end, in units of da.
-                let t1 = t0 + Position::dot(da, db) / da_2;
-                let (t_min, t_max) = t0.min_max(t1);
-                if t_min > C::one() || t_max < C::zero() {
-                    // if min(t0, t1) > 1 or max(t0, t1) < 0, they don't intersect.
-                    return SegmentIntersection::None;
-                } else {
-                    // Else, the intersect
-                    let start = self.start + da * t_min.max(C::zero());
-                    let end = self.start + da * t_max.min(C::one());
-                    if start == end {
-                        return SegmentIntersection::Position(start);
-                    } else {
-                        return SegmentIntersection::Segment(Segment::new(start, end));
-                    }
-                }
-            }
+                SegmentIntersection::None
+            };
+        }
+        // Offset, in units of da.
+        let t0 = Position::dot(offset, da) / da_2;
+        // self.start to other.end, in units of da.
+        let t1 = t0 + Position::dot(da, db) / da_2;
+        let ((t_min, p_min), (t_max, p_max)) = if t0 <= t1 {
+            ((t0, other.start), (t1, other.end))
         } else {
-            // The segments are not parallel, so they are disjoint or intersect at a point
-            // Calculate where the infinite lines would intersect; if these are on the segments
-            // then the segments intersect.
-            let ta = Position::cross(offset, db) / da_x_db;
-            let tb = offset_x_da / da_x_db;
-            if C::zero() <= ta && ta <= C::one() && C::zero() <= tb && tb <= C::one() {
-                return SegmentIntersection::Position(self.start + da * ta);
-            }
+            ((t1, other.end), (t0, other.start))
+        };
+        if t_min > C::one() || t_max < C::zero() {
+            // if min(t0, t1) > 1 or max(t0, t1) < 0, they don't intersect.
+            return SegmentIntersection::None;
+        }
+        // Else, the intersect.  The overlap runs between endpoints
+        // of the segments, which are used exactly.
+        let start = if t_min <= C::zero() {
+            self.start
+        } else {
+            p_min
+        };
+        let end = if t_max >= C::one() { self.end } else { p_max };
+        if start == end {
+            SegmentIntersection::Position(start)
+        } else {
+            SegmentIntersection::Segment(Segment::new(start, end))
         }
-        SegmentIntersection::None
     }
 
     /**
@@ -250,6 +312,21 @@ mod tests {
         assert_eq!(max_p.y, 3.0);
     }
 
+    #[test]
+    fn check_distance_to_position() {
+        let s = Segment::from(((0.0, 0.0), (2.0, 0.0)));
+        assert_eq!(s.distance_to_position((1.0, 1.0).into()), 1.0);
+        assert_eq!(s.distance_to_position((3.0, 0.0).into()), 1.0);
+        assert_eq!(s.distance_to_position((-3.0, 4.0).into()), 5.0);
+        assert_eq!(s.distance_to_position((0.5, 0.0).into()), 0.0);
+    }
+
+    #[test]
+    fn check_distance_to_position_degenerate() {
+        let s = Segment::from(((1.0, 1.0), (1.0, 1.0)));
+        assert_eq!(s.distance_to_position((4.0, 5.0).into()), 5.0);
+    }
+
     // Intersection tests
     /////////
 
@@ -343,4 +420,37 @@ mod tests {
             SegmentIntersection::Position((1.0, 0.0).into())
         );
     }
+
+    #[test]
+    fn check_intersect_segment_touching_exact() {
+        // (0.3, 0.7) is just right of the line through (0.9, 2.1), although
+        // the float cross product is zero, so the segments don't touch.
+        let s1 = Segment::from(((0.0, 0.0), (0.9, 2.1)));
+        let s2 = Segment::from(((0.3, 0.7), (1.0, 0.7)));
+        assert_eq!(s1.position_location(s2.start), PositionLocation::Right);
+        assert_eq!(s1.intersect_segment(s2), SegmentIntersection::None);
+        assert_eq!(s2.intersect_segment(s1), SegmentIntersection::None);
+        // An endpoint that is on the other segment is returned exactly.
+        let s3 = Segment::from(((0.0, 0.0), (3.0, 1.0)));
+        let s4 = Segment::from(((1.5, 0.5), (1.5, 2.0)));
+        assert_eq!(
+            s3.intersect_segment(s4),
+            SegmentIntersection::Position((1.5, 0.5).into())
+        );
+        assert_eq!(
+            s4.intersect_segment(s3),
+            SegmentIntersection::Position((1.5, 0.5).into())
+        );
+    }
+
+    #[test]
+    fn check_position_location_near_collinear() {
+        let s = Segment::from(((12.0, 12.0), (24.0, 24.0)));
+        let on = Position::new(0.5, 0.5);
+        let above = Position::new(0.5, 0.5 + f64::EPSILON);
+        let below = Position::new(0.5 + f64::EPSILON, 0.5);
+        assert_eq!(s.position_location(on), PositionLocation::On);
+        assert_eq!(s.position_location(above), PositionLocation::Left);
+        assert_eq!(s.position_location(below), PositionLocation::Right);
+    }
 }
@@ -1,8 +1,12 @@
-use crate::types::{
-    Coordinate, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
-};
-
-fn get_signed_loop_area<C: Coordinate>(ls: &LineString<C>) -> C {
+use crate::planar::types::{LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+use crate::Coordinate;
+
+/**
+ * The area enclosed by a loop, positive if it winds counterclockwise and
+ * negative if clockwise.  LineStrings with fewer than 4 positions enclose
+ * nothing.
+ */
+pub(crate) fn get_signed_loop_area<C: Coordinate>(ls: &LineString<C>) -> C {
     if ls.num_points() < 4 {
         return C::zero();
     }
@@ -19,6 +23,8 @@ fn get_loop_area<C: Coordinate>(ls: &LineString<C>) -> C {
     }
 }
 
+// Not part of the public API yet; only tests use it so far.
+#[allow(dead_code)]
 pub trait Area<C: Coordinate> {
     fn area(&self) -> C;
 }
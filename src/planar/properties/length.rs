@@ -1,5 +1,8 @@
-use crate::types::{Coordinate, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+use crate::planar::types::{MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+use crate::Coordinate;
 
+// Only the tests below use this until it is made public.
+#[allow(dead_code)]
 pub trait Length<C: Coordinate> {
     fn length(&self) -> C;
 }
@@ -45,7 +48,7 @@ impl<C: Coordinate> Length<C> for MultiPolygon<C>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::LineString;
+    use crate::planar::types::LineString;
 
     #[test]
     fn check_point() {
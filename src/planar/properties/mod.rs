@@ -1,3 +1,4 @@
-mod area;
+pub(crate) mod area;
 mod length;
-pub use crate::properties::{area::Area, length::Length};
+#[allow(unused_imports)]
+pub use crate::planar::properties::{area::Area, length::Length};
@@ -0,0 +1,365 @@
+use crate::planar::primitives::Position;
+use crate::planar::types::{
+    Empty, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+use crate::Coordinate;
+use std::convert::Infallible;
+use std::mem;
+
+/*
+ * Iterating over, and mapping, the positions of each geometry.
+ *
+ * The mapping functions rebuild each geometry with its `new` constructor,
+ * so that derived state (like the cached envelopes) is always consistent
+ * with the mapped positions.  Positions are visited in storage order: for
+ * polygons, the exterior and then each interior.
+ */
+
+/// Unwrap a result that can't be an error.
+fn _unwrap_infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+fn _empty_line_string<C: Coordinate>() -> LineString<C> {
+    LineString::new(Vec::new())
+}
+
+macro_rules! impl_map_coords {
+    // Implement the infallible mappings for a type, using its `try_map_coords`.
+    ($type:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /// Return a copy of the geometry with `f` applied to every position.
+            pub fn map_coords<F>(&self, mut f: F) -> $type<C>
+            where
+                F: FnMut(Position<C>) -> Position<C>,
+            {
+                _unwrap_infallible(self.try_map_coords(|p| Ok(f(p))))
+            }
+        }
+    };
+}
+
+impl<C: Coordinate> Empty<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> {
+        std::iter::empty()
+    }
+
+    pub fn try_map_coords<E, F>(&self, _f: F) -> Result<Empty<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        Ok(Empty::new())
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, _f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+    }
+}
+
+impl<C: Coordinate> Point<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> {
+        std::iter::once(self.0)
+    }
+
+    pub fn try_map_coords<E, F>(&self, mut f: F) -> Result<Point<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        Ok(Point(f(self.0)?))
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        self.0 = f(self.0);
+    }
+}
+
+impl<C: Coordinate> MultiPoint<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> + '_ {
+        self.points.iter().map(|p| p.0)
+    }
+
+    pub fn try_map_coords<E, F>(&self, mut f: F) -> Result<MultiPoint<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        let points = self
+            .points
+            .iter()
+            .map(|p| p.try_map_coords(&mut f))
+            .collect::<Result<_, E>>()?;
+        Ok(MultiPoint::new(points))
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        let mut points = mem::take(&mut self.points);
+        for point in points.iter_mut() {
+            point.map_coords_in_place(&mut f);
+        }
+        *self = MultiPoint::new(points);
+    }
+}
+
+impl<C: Coordinate> LineString<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> + '_ {
+        self.positions.iter().copied()
+    }
+
+    pub fn try_map_coords<E, F>(&self, f: F) -> Result<LineString<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        let positions = self
+            .positions
+            .iter()
+            .copied()
+            .map(f)
+            .collect::<Result<_, E>>()?;
+        Ok(LineString::new(positions))
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        let mut positions = mem::take(&mut self.positions);
+        for position in positions.iter_mut() {
+            *position = f(*position);
+        }
+        *self = LineString::new(positions);
+    }
+}
+
+impl<C: Coordinate> MultiLineString<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> + '_ {
+        self.line_strings.iter().flat_map(|ls| ls.coords_iter())
+    }
+
+    pub fn try_map_coords<E, F>(&self, mut f: F) -> Result<MultiLineString<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        let line_strings = self
+            .line_strings
+            .iter()
+            .map(|ls| ls.try_map_coords(&mut f))
+            .collect::<Result<_, E>>()?;
+        Ok(MultiLineString::new(line_strings))
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        let mut line_strings = mem::take(&mut self.line_strings);
+        for line_string in line_strings.iter_mut() {
+            line_string.map_coords_in_place(&mut f);
+        }
+        *self = MultiLineString::new(line_strings);
+    }
+}
+
+impl<C: Coordinate> Polygon<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> + '_ {
+        self.exterior
+            .coords_iter()
+            .chain(self.interiors.iter().flat_map(|ls| ls.coords_iter()))
+    }
+
+    pub fn try_map_coords<E, F>(&self, mut f: F) -> Result<Polygon<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        let exterior = self.exterior.try_map_coords(&mut f)?;
+        let interiors = self
+            .interiors
+            .iter()
+            .map(|ls| ls.try_map_coords(&mut f))
+            .collect::<Result<_, E>>()?;
+        Ok(Polygon::new(exterior, interiors))
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        let mut exterior = mem::replace(&mut self.exterior, _empty_line_string());
+        let mut interiors = mem::take(&mut self.interiors);
+        exterior.map_coords_in_place(&mut f);
+        for interior in interiors.iter_mut() {
+            interior.map_coords_in_place(&mut f);
+        }
+        *self = Polygon::new(exterior, interiors);
+    }
+}
+
+impl<C: Coordinate> MultiPolygon<C> {
+    pub fn coords_iter(&self) -> impl Iterator<Item = Position<C>> + '_ {
+        self.polygons.iter().flat_map(|p| p.coords_iter())
+    }
+
+    pub fn try_map_coords<E, F>(&self, mut f: F) -> Result<MultiPolygon<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        let polygons = self
+            .polygons
+            .iter()
+            .map(|p| p.try_map_coords(&mut f))
+            .collect::<Result<_, E>>()?;
+        Ok(MultiPolygon::new(polygons))
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        let mut polygons = mem::take(&mut self.polygons);
+        for polygon in polygons.iter_mut() {
+            polygon.map_coords_in_place(&mut f);
+        }
+        *self = MultiPolygon::new(polygons);
+    }
+}
+
+impl<C: Coordinate> Geometry<C> {
+    pub fn coords_iter(&self) -> Box<dyn Iterator<Item = Position<C>> + '_> {
+        match self {
+            Geometry::Empty(x) => Box::new(x.coords_iter()),
+            Geometry::Point(x) => Box::new(x.coords_iter()),
+            Geometry::MultiPoint(x) => Box::new(x.coords_iter()),
+            Geometry::LineString(x) => Box::new(x.coords_iter()),
+            Geometry::MultiLineString(x) => Box::new(x.coords_iter()),
+            Geometry::Polygon(x) => Box::new(x.coords_iter()),
+            Geometry::MultiPolygon(x) => Box::new(x.coords_iter()),
+        }
+    }
+
+    /// Map every position, keeping the geometry type, or return the first error.
+    pub fn try_map_coords<E, F>(&self, f: F) -> Result<Geometry<C>, E>
+    where
+        F: FnMut(Position<C>) -> Result<Position<C>, E>,
+    {
+        Ok(match self {
+            Geometry::Empty(x) => Geometry::from(x.try_map_coords(f)?),
+            Geometry::Point(x) => Geometry::from(x.try_map_coords(f)?),
+            Geometry::MultiPoint(x) => Geometry::from(x.try_map_coords(f)?),
+            Geometry::LineString(x) => Geometry::from(x.try_map_coords(f)?),
+            Geometry::MultiLineString(x) => Geometry::from(x.try_map_coords(f)?),
+            Geometry::Polygon(x) => Geometry::from(x.try_map_coords(f)?),
+            Geometry::MultiPolygon(x) => Geometry::from(x.try_map_coords(f)?),
+        })
+    }
+
+    pub fn map_coords_in_place<F>(&mut self, f: F)
+    where
+        F: FnMut(Position<C>) -> Position<C>,
+    {
+        match self {
+            Geometry::Empty(x) => x.map_coords_in_place(f),
+            Geometry::Point(x) => x.map_coords_in_place(f),
+            Geometry::MultiPoint(x) => x.map_coords_in_place(f),
+            Geometry::LineString(x) => x.map_coords_in_place(f),
+            Geometry::MultiLineString(x) => x.map_coords_in_place(f),
+            Geometry::Polygon(x) => x.map_coords_in_place(f),
+            Geometry::MultiPolygon(x) => x.map_coords_in_place(f),
+        }
+    }
+}
+
+impl_map_coords!(Empty);
+impl_map_coords!(Point);
+impl_map_coords!(MultiPoint);
+impl_map_coords!(LineString);
+impl_map_coords!(MultiLineString);
+impl_map_coords!(Polygon);
+impl_map_coords!(MultiPolygon);
+impl_map_coords!(Geometry);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::planar::primitives::{Envelope, HasEnvelope};
+
+    fn square_with_hole() -> Polygon<f64> {
+        Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (1., 2.),
+                (2., 2.),
+                (1., 1.),
+            ])],
+        )
+    }
+
+    #[test]
+    fn check_coords_iter_order() {
+        let polygon = square_with_hole();
+        let positions: Vec<Position<f64>> = polygon.coords_iter().collect();
+        assert_eq!(positions.len(), 9);
+        assert_eq!(positions[5], Position::new(1., 1.));
+        let geometry = Geometry::from(polygon);
+        assert_eq!(geometry.coords_iter().count(), 9);
+        assert_eq!(Geometry::<f64>::empty().coords_iter().count(), 0);
+    }
+
+    #[test]
+    fn check_map_coords_envelope() {
+        let mls = MultiLineString::from(vec![vec![(0., 0.), (1., 1.)], vec![(2., 0.), (3., 1.)]]);
+        let moved = mls.map_coords(|p| Position::new(p.x * 2., p.y - 1.));
+        assert_eq!(
+            moved.envelope(),
+            Envelope::new(Position::new(0., -1.), Position::new(6., 0.))
+        );
+        assert_eq!(moved.line_strings[1].envelope().min().unwrap().x, 4.);
+    }
+
+    #[test]
+    fn check_map_coords_in_place() {
+        let mut polygon = square_with_hole();
+        let expected = polygon.map_coords(|p| Position::new(p.y, -p.x));
+        polygon.map_coords_in_place(|p| Position::new(p.y, -p.x));
+        assert_eq!(polygon, expected);
+        assert_eq!(
+            polygon.envelope(),
+            Envelope::new(Position::new(0., -4.), Position::new(4., 0.))
+        );
+
+        let mut geometry = Geometry::from(MultiPolygon::new(vec![square_with_hole()]));
+        geometry.map_coords_in_place(|p| p + Position::new(10., 0.));
+        assert_eq!(geometry.envelope().min().unwrap().x, 10.);
+    }
+
+    #[test]
+    fn check_try_map_coords() {
+        let ls: LineString<f64> = LineString::from(vec![(0., 0.), (-1., 2.), (3., 3.)]);
+        let result = ls.try_map_coords(|p| {
+            if p.x < 0. {
+                Err("negative x")
+            } else {
+                Ok(Position::new(p.x.sqrt(), p.y))
+            }
+        });
+        assert_eq!(result, Err("negative x"));
+
+        let mut calls = 0;
+        let result: Result<_, ()> = Geometry::from(ls).try_map_coords(|p| {
+            calls += 1;
+            Ok(Position::new(p.x + 1., p.y))
+        });
+        assert_eq!(calls, 3);
+        assert_eq!(result.unwrap().envelope().min().unwrap().x, 0.);
+    }
+}
@@ -3,7 +3,7 @@ use crate::planar::types::Geometry;
 use crate::Coordinate;
 use std::marker::PhantomData;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Empty<C: Coordinate> {
     phantom: PhantomData<C>,
 }
@@ -37,10 +37,6 @@ impl<C: Coordinate> Empty<C> {
         true
     }
 
-    pub fn validate(&self) -> Result<(), &'static str> {
-        Ok(())
-    }
-
     pub fn boundary(&self) -> Geometry<C> {
         Geometry::empty()
     }
@@ -8,7 +8,7 @@ use crate::Coordinate;
 ///
 /// All `Geo` types can be converted to a `Geometry` member using `.into()` (as part of the
 /// `std::convert::Into` pattern).
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum Geometry<C: Coordinate> {
     Empty(Empty<C>),
     Point(Point<C>),
@@ -1,7 +1,9 @@
 use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
 use crate::planar::primitives::{Envelope, HasEnvelope, Position, Segment};
+use crate::planar::properties::area::get_signed_loop_area;
 use crate::planar::types::{Geometry, MultiPoint, Point};
 use crate::Coordinate;
+use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LineString<C: Coordinate> {
@@ -88,10 +90,55 @@ impl<C: Coordinate> LineString<C> {
         self.is_closed() && self.is_simple()
     }
 
+    /**
+     * Whether a closed LineString winds counterclockwise, so that it encloses
+     * a positive signed area.  LineStrings that aren't closed wind neither way.
+     */
+    pub fn is_ccw(&self) -> bool {
+        self.is_closed() && get_signed_loop_area(self) > C::zero()
+    }
+
+    /// Whether a closed LineString winds clockwise.
+    pub fn is_cw(&self) -> bool {
+        self.is_closed() && get_signed_loop_area(self) < C::zero()
+    }
+
     pub fn length(&self) -> C {
         self.segments_iter().map(|s| s.length()).sum()
     }
 
+    /**
+     * Split a closed LineString into simple loops at the positions it passes
+     * through more than once.
+     *
+     * Each loop is closed where the LineString returns to a position it has
+     * already visited, so nested loops come before the ones enclosing them.
+     * Loops with fewer than 4 positions are dropped.
+     */
+    pub(crate) fn _split_loops(&self) -> Vec<LineString<C>> {
+        let mut loops = Vec::new();
+        let mut stack: Vec<Position<C>> = Vec::with_capacity(self.positions.len());
+        let mut seen = HashMap::new();
+        for &position in &self.positions {
+            let key = position.to_hashable().unwrap();
+            if let Some(&start) = seen.get(&key) {
+                let mut ring = stack.split_off(start);
+                for p in &ring[1..] {
+                    seen.remove(&p.to_hashable().unwrap());
+                }
+                ring.push(position);
+                if ring.len() >= 4 {
+                    loops.push(LineString::new(ring));
+                }
+                stack.push(position);
+            } else {
+                seen.insert(key, stack.len());
+                stack.push(position);
+            }
+        }
+        loops
+    }
+
     /// Return the first coordinate of the linestring
     pub fn start_point(&self) -> Option<Point<C>> {
         let position = self.positions.first()?;
@@ -173,6 +220,32 @@ mod tests {
         )
     }
 
+    #[test]
+    fn check_split_loops() {
+        // A figure eight, touching itself at (1, 1), and a loop around it.
+        let ls = LineString::from(vec![
+            (0., 0.),
+            (1., 1.),
+            (2., 0.),
+            (2., 2.),
+            (1., 1.),
+            (0., 2.),
+            (0., 0.),
+        ]);
+        let loops = ls._split_loops();
+        assert_eq!(
+            loops,
+            vec![
+                LineString::from(vec![(1., 1.), (2., 0.), (2., 2.), (1., 1.)]),
+                LineString::from(vec![(0., 0.), (1., 1.), (0., 2.), (0., 0.)]),
+            ]
+        );
+        assert_eq!(
+            LineString::from(vec![(0., 0.), (1., 0.), (0., 0.)])._split_loops(),
+            vec![]
+        );
+    }
+
     #[test]
     fn check_is_empty() {
         let ls: LineString<f64> = LineString::new(vec![]);
@@ -215,6 +288,16 @@ mod tests {
         assert!(ls.is_closed());
     }
 
+    #[test]
+    fn check_is_ccw() {
+        let ccw = LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 0.)]);
+        assert!(ccw.is_ccw() && !ccw.is_cw());
+        let cw = LineString::from(vec![(0., 0.), (1., 1.), (1., 0.), (0., 0.)]);
+        assert!(cw.is_cw() && !cw.is_ccw());
+        let open = LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        assert!(!open.is_ccw() && !open.is_cw());
+    }
+
     #[test]
     fn check_envelope() {
         let ls = LineString::from(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (0.0, 0.0)]);
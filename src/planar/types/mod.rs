@@ -1,3 +1,4 @@
+mod coords;
 mod empty;
 mod geometry;
 mod line_string;
@@ -3,7 +3,7 @@ use crate::planar::types::{Geometry, LineString, MultiPoint};
 use crate::Coordinate;
 use std::collections::BTreeSet;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct MultiLineString<C: Coordinate> {
     pub line_strings: Vec<LineString<C>>,
     _envelope: Envelope<C>,
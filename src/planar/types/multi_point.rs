@@ -3,7 +3,7 @@ use crate::planar::types::{Geometry, Point};
 use crate::Coordinate;
 use std::collections::BTreeSet;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct MultiPoint<C: Coordinate> {
     pub points: Vec<Point<C>>,
     _envelope: Envelope<C>,
@@ -1,8 +1,8 @@
 use crate::planar::primitives::{Envelope, HasEnvelope};
-use crate::planar::types::{Geometry, MultiLineString, Point, Polygon};
+use crate::planar::types::{Geometry, MultiLineString, Polygon};
 use crate::Coordinate;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct MultiPolygon<C: Coordinate> {
     pub polygons: Vec<Polygon<C>>,
     _envelope: Envelope<C>,
@@ -16,6 +16,20 @@ impl<C: Coordinate> MultiPolygon<C> {
             _envelope,
         }
     }
+
+    /**
+     * Orient the rings of every polygon, so that exteriors wind
+     * counterclockwise if `exterior_ccw` is true, or else clockwise, and
+     * interiors wind the other way.  See `Polygon::orient`.
+     */
+    pub fn orient(&self, exterior_ccw: bool) -> MultiPolygon<C> {
+        MultiPolygon::new(
+            self.polygons
+                .iter()
+                .map(|polygon| polygon.orient(exterior_ccw))
+                .collect(),
+        )
+    }
 }
 
 /// Turn a `Vec` of `Polygon`-ish objects into a `MultiPolygon`.
@@ -25,19 +39,6 @@ impl<C: Coordinate, P: Into<Polygon<C>>> From<Vec<P>> for MultiPolygon<C> {
     }
 }
 
-// MultiPolygon implementation
-impl<C: Coordinate> MultiPolygon<C> {
-    pub fn centroid(&self) -> Point<C> {
-        // TODO: STUB
-        Point::from((C::zero(), C::zero()))
-    }
-
-    pub fn point_on_surface(&self) -> Option<Point<C>> {
-        let polys = &mut self.polygons.iter().filter(|p| !p.is_empty());
-        polys.next()?.point_on_surface()
-    }
-}
-
 impl<C: Coordinate> HasEnvelope<C> for MultiPolygon<C> {
     fn envelope(&self) -> Envelope<C> {
         self._envelope
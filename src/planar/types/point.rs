@@ -2,7 +2,7 @@ use crate::planar::primitives::{Envelope, HasEnvelope, Position};
 use crate::planar::types::Geometry;
 use crate::Coordinate;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Point<C: Coordinate>(pub Position<C>);
 
 impl<C: Coordinate> HasEnvelope<C> for Point<C> {
@@ -1,8 +1,8 @@
 use crate::planar::primitives::{Envelope, HasEnvelope};
-use crate::planar::types::{Geometry, LineString, MultiLineString, Point};
+use crate::planar::types::{Geometry, LineString, MultiLineString};
 use crate::Coordinate;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Polygon<C: Coordinate> {
     pub exterior: LineString<C>,
     pub interiors: Vec<LineString<C>>,
@@ -36,21 +36,29 @@ impl<C: Coordinate> Polygon<C> {
     pub fn is_simple(&self) -> bool {
         self.validate().is_ok()
     }
-}
-
-// Polygon implementation
-impl<C: Coordinate> Polygon<C> {
-    pub fn centroid(&self) -> Point<C> {
-        // TODO: STUB
-        Point::from((C::zero(), C::zero()))
-    }
 
     /**
-     * Find an abitrary point on the surface.
-     * If empty, return None.
+     * Orient the rings, so that the exterior winds counterclockwise if
+     * `exterior_ccw` is true, or else clockwise, and the interiors wind the
+     * other way.  RFC 7946 (GeoJSON) asks for counterclockwise exteriors.
+     * Rings that aren't closed or enclose no area are left as they are.
      */
-    pub fn point_on_surface(&self) -> Option<Point<C>> {
-        self.exterior.start_point()
+    pub fn orient(&self, exterior_ccw: bool) -> Polygon<C> {
+        Polygon::new(
+            _orient_ring(&self.exterior, exterior_ccw),
+            self.interiors
+                .iter()
+                .map(|ring| _orient_ring(ring, !exterior_ccw))
+                .collect(),
+        )
+    }
+}
+
+fn _orient_ring<C: Coordinate>(ring: &LineString<C>, ccw: bool) -> LineString<C> {
+    if (ccw && ring.is_cw()) || (!ccw && ring.is_ccw()) {
+        LineString::new(ring.positions.iter().rev().copied().collect())
+    } else {
+        ring.clone()
     }
 }
 
@@ -148,4 +156,24 @@ mod tests {
 
         assert!(!poly.is_simple());
     }
+
+    #[test]
+    fn check_orient() {
+        let poly = Polygon::new(
+            LineString::from(vec![(0., 0.), (0., 4.), (4., 4.), (4., 0.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.), (1., 1.)]),
+                LineString::from(vec![(3., 3.), (3.5, 3.5), (3.5, 3.), (3., 3.)]),
+            ],
+        );
+        let oriented = poly.orient(true);
+        assert!(oriented.exterior.is_ccw());
+        assert!(oriented.interiors.iter().all(|ring| ring.is_cw()));
+        assert_eq!(oriented.exterior.positions[0], poly.exterior.positions[0]);
+        assert_eq!(oriented.interiors[1], poly.interiors[1]);
+        assert_eq!(oriented.orient(false), poly.orient(false));
+        let oriented = poly.orient(false);
+        assert!(oriented.exterior.is_cw());
+        assert!(oriented.interiors.iter().all(|ring| ring.is_ccw()));
+    }
 }
@@ -0,0 +1,15 @@
+use crate::planar::types::Empty;
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::ValidationError;
+use crate::Coordinate;
+
+impl<C: Coordinate> Empty<C> {
+    pub(crate) fn _validate(
+        &self,
+        _reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
+        Ok(())
+    }
+}
+
+impl_validation!(Empty);
@@ -0,0 +1,140 @@
+use crate::planar::primitives::{Position, PrecisionModel};
+use crate::Coordinate;
+use std::fmt;
+
+/// The kinds of defect that make a geometry invalid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationErrorKind {
+    /// A position has an infinite or NaN coordinate.
+    NonFiniteCoordinate,
+    /// A non-empty LineString has only one position.
+    TooFewPoints,
+    /// A LineString has two adjacent positions that are the same.
+    RepeatedPoint,
+    /// A LineString intersects itself.
+    SelfIntersection,
+    /// A polygon ring doesn't end where it starts.
+    RingNotClosed,
+    /// An interior ring is not inside the exterior ring.
+    HoleOutsideShell,
+    /// Two interior rings intersect, or one is inside the other.
+    HolesIntersect,
+    /// A MultiPoint has the same point twice.
+    DuplicatePoint,
+    /// Two parts of a multi-geometry intersect.
+    PartsIntersect,
+    /// A polygon ring winds the wrong way for the validity options.
+    WrongOrientation,
+}
+
+impl ValidationErrorKind {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ValidationErrorKind::NonFiniteCoordinate => "Position is not finite.",
+            ValidationErrorKind::TooFewPoints => "LineString must have at least 2 points.",
+            ValidationErrorKind::RepeatedPoint => "LineString has repeated points.",
+            ValidationErrorKind::SelfIntersection => "LineString has self-intersection.",
+            ValidationErrorKind::RingNotClosed => "Ring is not a loop.",
+            ValidationErrorKind::HoleOutsideShell => {
+                "Interior loop not contained in exterior loop."
+            }
+            ValidationErrorKind::HolesIntersect => "Two interior rings intersect.",
+            ValidationErrorKind::DuplicatePoint => "Duplicate point.",
+            ValidationErrorKind::PartsIntersect => "Two parts intersect.",
+            ValidationErrorKind::WrongOrientation => "Ring has the wrong orientation.",
+        }
+    }
+}
+
+/**
+ * A defect that makes a geometry invalid, and where it is.
+ *
+ * `part` is the index of the offending part of a multi-geometry, and `ring`
+ * the index of the offending ring of a polygon: 0 for the exterior, and
+ * i + 1 for `interiors[i]`.  For defects between two parts or rings, `other`
+ * is the index of the second one, at the same level.  `position` is where the
+ * defect is, when there is a single place to point to.
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ValidationError<C: Coordinate> {
+    pub kind: ValidationErrorKind,
+    pub part: Option<usize>,
+    pub ring: Option<usize>,
+    pub other: Option<usize>,
+    pub position: Option<Position<C>>,
+}
+
+impl<C: Coordinate> ValidationError<C> {
+    pub fn new(kind: ValidationErrorKind, position: Option<Position<C>>) -> Self {
+        ValidationError {
+            kind,
+            part: None,
+            ring: None,
+            other: None,
+            position,
+        }
+    }
+
+    /// Round the position of the defect to a precision model.
+    pub(crate) fn _make_precise(mut self, precision_model: &PrecisionModel<C>) -> Self {
+        self.position = self
+            .position
+            .map(|p| precision_model.make_precise_position(p));
+        self
+    }
+
+    pub fn with_part(mut self, part: usize) -> Self {
+        self.part = Some(part);
+        self
+    }
+
+    pub fn with_ring(mut self, ring: usize) -> Self {
+        self.ring = Some(ring);
+        self
+    }
+
+    pub fn with_other(mut self, other: usize) -> Self {
+        self.other = Some(other);
+        self
+    }
+}
+
+impl<C: Coordinate> fmt::Display for ValidationError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind.description())?;
+        if let Some(part) = self.part {
+            write!(f, " Part {}.", part)?;
+        }
+        if let Some(ring) = self.ring {
+            write!(f, " Ring {}.", ring)?;
+        }
+        if let Some(other) = self.other {
+            write!(f, " Other {}.", other)?;
+        }
+        if let Some(position) = self.position {
+            write!(f, " At ({:?}, {:?}).", position.x, position.y)?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: Coordinate> std::error::Error for ValidationError<C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_display() {
+        let error = ValidationError::new(
+            ValidationErrorKind::SelfIntersection,
+            Some(Position::new(1.5, 2.)),
+        )
+        .with_part(3)
+        .with_ring(0);
+        assert_eq!(
+            error.to_string(),
+            "LineString has self-intersection. Part 3. Ring 0. At (1.5, 2.0)."
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use crate::planar::types::Geometry;
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::ValidationError;
+use crate::Coordinate;
+
+impl<C: Coordinate> Geometry<C> {
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
+        match self {
+            Geometry::Empty(x) => x._validate(reporter),
+            Geometry::Point(x) => x._validate(reporter),
+            Geometry::MultiPoint(x) => x._validate(reporter),
+            Geometry::LineString(x) => x._validate(reporter),
+            Geometry::MultiLineString(x) => x._validate(reporter),
+            Geometry::Polygon(x) => x._validate(reporter),
+            Geometry::MultiPolygon(x) => x._validate(reporter),
+        }
+    }
+}
+
+impl_validation!(Geometry);
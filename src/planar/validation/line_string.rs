@@ -1,59 +1,94 @@
-use crate::flatbush::Flatbush;
-use crate::planar::primitives::SegmentIntersection;
+use crate::planar::primitives::{HasEnvelope, Position, SafePosition, SegmentIntersection};
 use crate::planar::types::LineString;
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::{ValidationError, ValidationErrorKind};
 use crate::Coordinate;
+use std::collections::HashSet;
 
 impl<C: Coordinate> LineString<C> {
     /**
-     * Validate the geometry.
+     * The workhouse fn for validation.
      *
      * A LineString is valid if it has 2 or more positions, has no repeated
      * positions, and has no self-intersections, except possibly last_point
      * and first_point being the same.
+     *
+     * The validity options can allow repeated positions, which are then
+     * ignored, and allow polygon rings to touch themselves at a position.
      */
-    pub fn validate(&self) -> Result<(), &'static str> {
-        match self._validate_with_rtree() {
-            Err(s) => Err(s),
-            _ => Ok(()),
-        }
-    }
-
-    /*
-     * The workhouse fn for validation.
-     * It does the work, but also returns the constructed rtree, which can be
-     * used for additional validation checks, eg for MultiLineString.
-     */
-    pub(crate) fn _validate_with_rtree(&self) -> Result<Flatbush<C>, &'static str> {
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
         if self.is_empty() {
             // LineStrings with no points are valid empty geometries
-            return Ok(Flatbush::new_empty());
+            return Ok(());
         } else if self.num_points() == 1 {
             // Must have at least 2 points to be 1-dimensional.
-            return Err("LineString must have at least 2 points.");
+            return reporter.report(ValidationError::new(
+                ValidationErrorKind::TooFewPoints,
+                Some(self.positions[0]),
+            ));
         }
 
-        // Declare the errors here
-        let repeated_err = Err("LineString has repeated points.");
-        let intersection_err = Err("LineString has self-intersection.");
+        let mut is_finite = true;
+        let mut has_repeats = false;
+        for (index, &position) in self.positions.iter().enumerate() {
+            if position.validate().is_err() {
+                // First check: should have finite coordinates.
+                is_finite = false;
+                reporter.report(ValidationError::new(
+                    ValidationErrorKind::NonFiniteCoordinate,
+                    Some(position),
+                ))?;
+            } else if index > 0 && position == self.positions[index - 1] {
+                // Second check: should not have two same adjacent points.
+                has_repeats = true;
+                if !reporter.options.allow_repeated_points {
+                    reporter.report(ValidationError::new(
+                        ValidationErrorKind::RepeatedPoint,
+                        Some(position),
+                    ))?;
+                }
+            }
+        }
 
-        for seg in self.segments_iter() {
-            // First check: should have finite coordinates.
-            seg.start.validate()?;
-            seg.end.validate()?;
-            // Second check: should not have two same adjacent points.
-            if seg.start == seg.end {
-                return repeated_err;
+        if !is_finite {
+            // Intersections can't be found without finite positions.
+            return Ok(());
+        }
+        if has_repeats {
+            // Degenerate segments would show up as spurious self-intersections.
+            let deduped = self._without_repeated_points();
+            if reporter.options.allow_repeated_points && deduped.num_points() == 1 {
+                return reporter.report(ValidationError::new(
+                    ValidationErrorKind::TooFewPoints,
+                    Some(self.positions[0]),
+                ));
             }
+            return deduped._report_self_intersections(reporter);
         }
+        self._report_self_intersections(reporter)
+    }
 
+    fn _report_self_intersections(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
+        if self.num_points() < 2 {
+            return Ok(());
+        }
         let rtree = self.build_rtree();
         let intersections = rtree.find_self_intersection_candidates();
 
+        // Several pairs of segments can cross at the same position.
+        let mut reported: HashSet<SafePosition<C>> = HashSet::new();
         let num_segments = self.num_points() - 1;
+        let allow_touches = reporter.ring.is_some() && reporter.options.allow_self_touching_rings;
         for (low_id, high_id) in intersections {
             let first_segment = self.get_segment(low_id);
             let second_segment = self.get_segment(high_id);
-            match first_segment.intersect_segment(second_segment) {
+            let position = match first_segment.intersect_segment(second_segment) {
                 SegmentIntersection::None => continue,
                 SegmentIntersection::Position(p) => {
                     // Point intersections are fine at the shared point between
@@ -62,20 +97,70 @@ impl<C: Coordinate> LineString<C> {
                         && (p == first_segment.end || p == first_segment.start)
                     {
                         continue;
-                    } else {
-                        return intersection_err;
                     }
+                    // Rings may be allowed to pass through a position twice.
+                    if allow_touches
+                        && [first_segment.start, first_segment.end]
+                            .iter()
+                            .any(|&q| q == second_segment.start || q == second_segment.end)
+                    {
+                        continue;
+                    }
+                    p
                 }
-                SegmentIntersection::Segment(_) => {
-                    // Segment intersxns are always bad
-                    return intersection_err;
+                // Segment intersxns are always bad
+                SegmentIntersection::Segment(s) => s.start,
+            };
+            if let Ok(key) = position.to_hashable() {
+                if !reported.insert(key) {
+                    continue;
                 }
             }
+            reporter.report(ValidationError::new(
+                ValidationErrorKind::SelfIntersection,
+                Some(position),
+            ))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn _has_repeated_points(&self) -> bool {
+        self.positions.windows(2).any(|w| w[0] == w[1])
+    }
+
+    /// A copy without adjacent repeated positions.
+    pub(crate) fn _without_repeated_points(&self) -> LineString<C> {
+        let mut positions = self.positions.clone();
+        positions.dedup();
+        LineString::new(positions)
+    }
+
+    /**
+     * Find a position where this intersects `other`, if there is one.
+     *
+     * Overlaps are reported by the start of the shared segment.
+     */
+    pub(crate) fn _find_intersection_position(&self, other: &LineString<C>) -> Option<Position<C>> {
+        if !self.envelope().intersects(other.envelope()) {
+            return None;
+        }
+        let (rtree1, rtree2) = (self.build_rtree(), other.build_rtree());
+        for (seg1_id, seg2_id) in rtree1.find_other_rtree_intersection_candidates(&rtree2) {
+            match self
+                .get_segment(seg1_id)
+                .intersect_segment(other.get_segment(seg2_id))
+            {
+                SegmentIntersection::None => continue,
+                SegmentIntersection::Position(p) => return Some(p),
+                SegmentIntersection::Segment(s) => return Some(s.start),
+            }
         }
-        Ok(rtree)
+        None
     }
 }
 
+impl_validation!(LineString);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +205,19 @@ mod tests {
                 .is_err()
         );
     }
+
+    #[test]
+    fn check_error_location() {
+        let error = LineString::from(vec![(1., 4.), (3., 4.), (3., 0.), (2., 8.)])
+            .validate()
+            .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::SelfIntersection);
+        assert_eq!(error.position, Some(Position::new(2.5, 4.)));
+
+        let error = LineString::from(vec![(0., 0.), (1., 1.), (1., 1.)])
+            .validate()
+            .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::RepeatedPoint);
+        assert_eq!(error.position, Some(Position::new(1., 1.)));
+    }
 }
@@ -1,6 +1,16 @@
+#[macro_use]
+mod report;
+
+mod empty;
+mod error;
+mod geometry;
 mod line_string;
 mod multi_line_string;
 mod multi_point;
 mod multi_polygon;
+mod options;
 mod point;
 mod polygon;
+
+pub use crate::planar::validation::error::{ValidationError, ValidationErrorKind};
+pub use crate::planar::validation::options::{RingOrientation, ValidityOptions};
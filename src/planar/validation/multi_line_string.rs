@@ -1,24 +1,64 @@
 use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
-use crate::planar::primitives::SegmentIntersection;
+use crate::planar::primitives::{
+    Envelope, HasEnvelope, Position, SafePosition, SegmentIntersection,
+};
 use crate::planar::types::{Geometry, MultiLineString, Point};
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::{ValidationError, ValidationErrorKind};
 use crate::Coordinate;
+use std::collections::HashSet;
 
 impl<C: Coordinate> MultiLineString<C> {
-    pub fn validate(&self) -> Result<(), &'static str> {
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
         if self.line_strings.is_empty() {
             // Empty multilinestrings are valid empty geometries.
             return Ok(());
         }
-        let intersection_err = Err("Intersection between LineStrings.");
+        if reporter.options.allow_repeated_points
+            && self.line_strings.iter().any(|ls| ls._has_repeated_points())
+        {
+            // The linestrings are checked against each other without them.
+            let linestrings = self.line_strings.iter();
+            let deduped = linestrings.map(|ls| ls._without_repeated_points());
+            return MultiLineString::new(deduped.collect())._validate(reporter);
+        }
 
-        for linestring in &self.line_strings {
-            linestring.validate()?;
+        // Linestrings can only be checked against each other if they're finite.
+        let mut envelopes = Vec::with_capacity(self.line_strings.len());
+        for (index, linestring) in self.line_strings.iter().enumerate() {
+            reporter.part = Some(index);
+            linestring._validate(reporter)?;
+            envelopes.push(
+                if linestring.positions.iter().all(|p| p.validate().is_ok()) {
+                    linestring.envelope()
+                } else {
+                    Envelope::empty()
+                },
+            );
         }
-        let rtree_of_linestrings = Flatbush::new(&self.line_strings, FLATBUSH_DEFAULT_DEGREE);
+        reporter.part = None;
+        let rtree_of_linestrings = Flatbush::new(&envelopes, FLATBUSH_DEFAULT_DEGREE);
 
         for (ls1_id, ls2_id) in rtree_of_linestrings.find_self_intersection_candidates() {
             let linestring1 = &self.line_strings[ls1_id];
             let linestring2 = &self.line_strings[ls2_id];
+            // Several pairs of segments can cross at the same position.
+            let mut reported: HashSet<SafePosition<C>> = HashSet::new();
+            let mut report_intersection = |position: Position<C>| {
+                if let Ok(key) = position.to_hashable() {
+                    if !reported.insert(key) {
+                        return Ok(());
+                    }
+                }
+                reporter.report(
+                    ValidationError::new(ValidationErrorKind::PartsIntersect, Some(position))
+                        .with_part(ls1_id)
+                        .with_other(ls2_id),
+                )
+            };
             let rtree1 = linestring1.build_rtree();
             let rtree2 = linestring2.build_rtree();
             for (seg1_id, seg2_id) in rtree1.find_other_rtree_intersection_candidates(&rtree2) {
@@ -26,8 +66,8 @@ impl<C: Coordinate> MultiLineString<C> {
                 let seg2 = linestring2.get_segment(seg2_id);
                 match seg1.intersect_segment(seg2) {
                     SegmentIntersection::None => continue,
-                    SegmentIntersection::Segment(_) => {
-                        return intersection_err;
+                    SegmentIntersection::Segment(s) => {
+                        report_intersection(s.start)?;
                     }
                     SegmentIntersection::Position(pos) => {
                         // Allow linestrings to intersect at their endpoints.
@@ -36,10 +76,10 @@ impl<C: Coordinate> MultiLineString<C> {
                         {
                             let point = Point(pos);
                             if !(mp1.contains_point(&point) && mp2.contains_point(&point)) {
-                                return intersection_err;
+                                report_intersection(pos)?;
                             }
                         } else {
-                            return intersection_err;
+                            report_intersection(pos)?;
                         }
                     }
                 }
@@ -49,9 +89,12 @@ impl<C: Coordinate> MultiLineString<C> {
     }
 }
 
+impl_validation!(MultiLineString);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::planar::primitives::Position;
     use crate::planar::types::LineString;
 
     #[test]
@@ -91,4 +134,41 @@ mod tests {
                 .is_err()
         );
     }
+
+    #[test]
+    fn check_validation_report() {
+        let mls = MultiLineString::from(vec![
+            vec![(0., 0.), (4., 4.), (4., 0.), (0., 4.)],
+            vec![(10., 0.), (10., 0.), (11., 1.)],
+            vec![(2., -1.), (2., 5.)],
+        ]);
+        let summary: Vec<_> = mls
+            .validation_report()
+            .iter()
+            .map(|e| (e.kind, e.part, e.other, e.position.unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    ValidationErrorKind::SelfIntersection,
+                    Some(0),
+                    None,
+                    Position::new(2., 2.)
+                ),
+                (
+                    ValidationErrorKind::RepeatedPoint,
+                    Some(1),
+                    None,
+                    Position::new(10., 0.)
+                ),
+                (
+                    ValidationErrorKind::PartsIntersect,
+                    Some(0),
+                    Some(2),
+                    Position::new(2., 2.)
+                ),
+            ]
+        );
+    }
 }
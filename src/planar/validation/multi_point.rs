@@ -1,7 +1,8 @@
 use crate::planar::types::MultiPoint;
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::{ValidationError, ValidationErrorKind};
 use crate::Coordinate;
-use ordered_float::FloatIsNan;
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 impl<C: Coordinate> MultiPoint<C> {
     /**
@@ -11,21 +12,33 @@ impl<C: Coordinate> MultiPoint<C> {
      * 1. is empty, or
      * 2. has no invalid points and no duplicate points.
      */
-    pub fn validate(&self) -> Result<(), &'static str> {
-        let mut position_set = HashSet::new();
-        for point in &self.points {
-            point.validate()?;
-            match point.0.to_hashable() {
-                Err(FloatIsNan) => return Err("Point contains NaN."),
-                Ok(hashable) => {
-                    if position_set.contains(&hashable) {
-                        return Err("Duplicate point");
-                    } else {
-                        position_set.insert(hashable);
-                    }
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
+        let mut position_indices = HashMap::new();
+        for (index, point) in self.points.iter().enumerate() {
+            reporter.part = Some(index);
+            let num_errors = reporter.num_errors();
+            point._validate(reporter)?;
+            if reporter.num_errors() > num_errors {
+                continue;
+            }
+            // Finite positions can always be hashed.
+            if let Ok(hashable) = point.0.to_hashable() {
+                if let Some(&first) = position_indices.get(&hashable) {
+                    reporter.report(
+                        ValidationError::new(ValidationErrorKind::DuplicatePoint, Some(point.0))
+                            .with_other(first),
+                    )?;
+                } else {
+                    position_indices.insert(hashable, index);
                 }
             }
         }
+        reporter.part = None;
         Ok(())
     }
 }
+
+impl_validation!(MultiPoint);
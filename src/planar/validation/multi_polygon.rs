@@ -1,60 +1,54 @@
 use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
 use crate::planar::algorithms::loop_relation::{find_loop_loop_relation, LoopLoopRelation};
-use crate::planar::types::MultiPolygon;
+use crate::planar::primitives::{Envelope, HasEnvelope};
+use crate::planar::types::{MultiPolygon, Polygon};
+use crate::planar::validation::polygon::_find_defect_position;
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::{ValidationError, ValidationErrorKind};
 use crate::Coordinate;
 
 impl<C: Coordinate> MultiPolygon<C> {
-    pub fn validate(&self) -> Result<(), &'static str> {
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
         if self.polygons.is_empty() {
             // MultiPolygons with no Polygons are a valid empty geometry.
             return Ok(());
         }
+        if reporter.options.allow_repeated_points
+            && self.polygons.iter().any(|p| p._has_repeated_points())
+        {
+            // The polygons are checked against each other without them.
+            let deduped = self.polygons.iter().map(|p| p._without_repeated_points());
+            return MultiPolygon::new(deduped.collect())._validate(reporter);
+        }
 
-        let intersection_err = Err("Two polygons intersect.");
-
-        for polygon in self.polygons.iter() {
-            polygon.validate()?;
+        // Only valid polygons are checked against each other.
+        let mut envelopes = Vec::with_capacity(self.polygons.len());
+        for (index, polygon) in self.polygons.iter().enumerate() {
+            reporter.part = Some(index);
+            let num_errors = reporter.num_errors();
+            polygon._validate(reporter)?;
+            envelopes.push(if reporter.num_errors() == num_errors {
+                polygon.envelope()
+            } else {
+                Envelope::empty()
+            });
         }
-        let rtree_of_polygons = Flatbush::new(&self.polygons, FLATBUSH_DEFAULT_DEGREE);
+        reporter.part = None;
+        let rtree_of_polygons = Flatbush::new(&envelopes, FLATBUSH_DEFAULT_DEGREE);
 
         for (poly1_id, poly2_id) in rtree_of_polygons.find_self_intersection_candidates() {
             let polygon1 = &self.polygons[poly1_id];
             let polygon2 = &self.polygons[poly2_id];
-            let inner_poly;
-            let outer_poly;
-            match find_loop_loop_relation(&polygon1.exterior, &polygon2.exterior) {
-                LoopLoopRelation::Separate => continue,
-                LoopLoopRelation::Crosses => return intersection_err,
-                LoopLoopRelation::Contains => {
-                    inner_poly = polygon2;
-                    outer_poly = polygon1;
-                }
-                LoopLoopRelation::Within => {
-                    inner_poly = polygon1;
-                    outer_poly = polygon2;
-                }
-            }
-            // If inner_poly.exterior is contained within outer_poly.exterior,
-            // inner_poly must be inside of exactly one loop in outer_poly.interiors.
-            // Validity ensures that there is at most one like this.
-            // Crosses or Contains means this is invalid.  Separate means that
-            // inner_poly might be in another interior loop.
-            let mut inside_interior = false;
-            for int_loop in &outer_poly.interiors {
-                match find_loop_loop_relation(&inner_poly.exterior, &int_loop) {
-                    LoopLoopRelation::Separate => continue,
-                    LoopLoopRelation::Within => inside_interior = true,
-                    LoopLoopRelation::Crosses | LoopLoopRelation::Contains => {
-                        return intersection_err
-                    }
-                }
-                if inside_interior {
-                    break;
-                }
-            }
-            if !inside_interior {
-                // We didn't find any interior loop that inner_poly is contained in.
-                return intersection_err;
+            if _polygons_intersect(polygon1, polygon2) {
+                let position = _find_defect_position(&polygon1.exterior, &polygon2.exterior);
+                reporter.report(
+                    ValidationError::new(ValidationErrorKind::PartsIntersect, position)
+                        .with_part(poly1_id)
+                        .with_other(poly2_id),
+                )?;
             }
         }
 
@@ -62,9 +56,44 @@ impl<C: Coordinate> MultiPolygon<C> {
     }
 }
 
+impl_validation!(MultiPolygon);
+
+/// Check whether two valid polygons intersect, other than at finitely many points.
+fn _polygons_intersect<C: Coordinate>(polygon1: &Polygon<C>, polygon2: &Polygon<C>) -> bool {
+    let inner_poly;
+    let outer_poly;
+    match find_loop_loop_relation(&polygon1.exterior, &polygon2.exterior) {
+        LoopLoopRelation::Separate => return false,
+        LoopLoopRelation::Crosses => return true,
+        LoopLoopRelation::Contains => {
+            inner_poly = polygon2;
+            outer_poly = polygon1;
+        }
+        LoopLoopRelation::Within => {
+            inner_poly = polygon1;
+            outer_poly = polygon2;
+        }
+    }
+    // If inner_poly.exterior is contained within outer_poly.exterior,
+    // inner_poly must be inside of exactly one loop in outer_poly.interiors.
+    // Validity ensures that there is at most one like this.
+    // Crosses or Contains means this is invalid.  Separate means that
+    // inner_poly might be in another interior loop.
+    for int_loop in &outer_poly.interiors {
+        match find_loop_loop_relation(&inner_poly.exterior, &int_loop) {
+            LoopLoopRelation::Separate => continue,
+            LoopLoopRelation::Within => return false,
+            LoopLoopRelation::Crosses | LoopLoopRelation::Contains => return true,
+        }
+    }
+    // We didn't find any interior loop that inner_poly is contained in.
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::planar::primitives::Position;
     use crate::planar::types::{LineString, Polygon};
 
     #[test]
@@ -108,4 +137,71 @@ mod tests {
         .validate()
         .is_err());
     }
+
+    #[test]
+    fn check_error_location() {
+        let error = MultiPolygon::from(vec![
+            Polygon::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]),
+            Polygon::from(vec![(5., 5.), (6., 5.), (6., 6.), (5., 5.)]),
+            Polygon::from(vec![(1., 1.), (3., 1.), (3., 3.), (1., 3.), (1., 1.)]),
+        ])
+        .validate()
+        .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::PartsIntersect);
+        assert_eq!((error.part, error.other), (Some(0), Some(2)));
+        let position = error.position.unwrap();
+        assert!(position == Position::new(2., 1.) || position == Position::new(1., 2.));
+    }
+
+    #[test]
+    fn check_validation_report() {
+        let mp = MultiPolygon::from(vec![
+            // A repeated point, and a hole that isn't closed.
+            Polygon::new(
+                LineString::from(vec![
+                    (0., 0.),
+                    (4., 0.),
+                    (4., 0.),
+                    (4., 4.),
+                    (0., 4.),
+                    (0., 0.),
+                ]),
+                vec![LineString::from(vec![(1., 1.), (2., 1.), (2., 2.)])],
+            ),
+            // A bowtie.
+            Polygon::from(vec![(10., 0.), (12., 2.), (12., 0.), (10., 2.), (10., 0.)]),
+            // Two overlapping squares.
+            Polygon::from(vec![(20., 0.), (22., 0.), (22., 2.), (20., 2.), (20., 0.)]),
+            Polygon::from(vec![(21., 1.), (23., 1.), (23., 3.), (21., 3.), (21., 1.)]),
+        ]);
+        let report = mp.validation_report();
+        let summary: Vec<_> = report
+            .iter()
+            .map(|e| (e.kind, e.part, e.ring, e.other))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ValidationErrorKind::RepeatedPoint, Some(0), Some(0), None),
+                (ValidationErrorKind::RingNotClosed, Some(0), Some(1), None),
+                (
+                    ValidationErrorKind::SelfIntersection,
+                    Some(1),
+                    Some(0),
+                    None
+                ),
+                (ValidationErrorKind::PartsIntersect, Some(2), None, Some(3)),
+            ]
+        );
+        assert_eq!(report[2].position, Some(Position::new(11., 1.)));
+        assert_eq!(mp.validate(), Err(report[0]));
+
+        let valid = MultiPolygon::from(vec![Polygon::from(vec![
+            (0., 0.),
+            (1., 0.),
+            (1., 1.),
+            (0., 0.),
+        ])]);
+        assert!(valid.validation_report().is_empty());
+    }
 }
@@ -0,0 +1,67 @@
+/// Which way polygon rings must wind to be valid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RingOrientation {
+    /// Rings may wind either way, as in the OGC Simple Features model.
+    Any,
+    /// Exteriors wind clockwise and interiors counterclockwise, as in shapefiles.
+    ExteriorClockwise,
+    /// Exteriors wind counterclockwise and interiors clockwise, as RFC 7946
+    /// (GeoJSON) requires.
+    ExteriorCounterClockwise,
+}
+
+/**
+ * Which validity model to check geometries against.
+ *
+ * The default is the OGC Simple Features model.  Shapefile (ESRI) data
+ * follows a looser model, where a ring may touch itself at a position to
+ * enclose a hole, and repeated positions are allowed, but rings must be
+ * oriented.
+ *
+ * `allow_self_touching_rings`: A polygon ring may pass through the same
+ *   position more than once, as long as the loops this splits it into don't
+ *   cross.  For an exterior, the loops inside the outermost one are holes.
+ * `ring_orientation`: The way exteriors and interiors must wind.
+ * `allow_repeated_points`: Adjacent positions may be the same.  They are
+ *   ignored, so a LineString still needs two distinct positions.
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValidityOptions {
+    pub allow_self_touching_rings: bool,
+    pub ring_orientation: RingOrientation,
+    pub allow_repeated_points: bool,
+}
+
+impl ValidityOptions {
+    /// The OGC Simple Features validity model.
+    pub fn ogc() -> Self {
+        ValidityOptions {
+            allow_self_touching_rings: false,
+            ring_orientation: RingOrientation::Any,
+            allow_repeated_points: false,
+        }
+    }
+
+    /// The shapefile validity model, with clockwise exteriors.
+    pub fn esri() -> Self {
+        ValidityOptions {
+            allow_self_touching_rings: true,
+            ring_orientation: RingOrientation::ExteriorClockwise,
+            allow_repeated_points: true,
+        }
+    }
+
+    /// The OGC model, with the counterclockwise exteriors of RFC 7946.
+    pub fn rfc7946() -> Self {
+        ValidityOptions {
+            ring_orientation: RingOrientation::ExteriorCounterClockwise,
+            ..ValidityOptions::ogc()
+        }
+    }
+}
+
+impl Default for ValidityOptions {
+    fn default() -> Self {
+        ValidityOptions::ogc()
+    }
+}
@@ -1,8 +1,21 @@
 use crate::planar::types::Point;
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::{ValidationError, ValidationErrorKind};
 use crate::Coordinate;
 
 impl<C: Coordinate> Point<C> {
-    pub fn validate(&self) -> Result<(), &'static str> {
-        self.0.validate()
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
+        if self.0.validate().is_err() {
+            reporter.report(ValidationError::new(
+                ValidationErrorKind::NonFiniteCoordinate,
+                Some(self.0),
+            ))?;
+        }
+        Ok(())
     }
 }
+
+impl_validation!(Point);
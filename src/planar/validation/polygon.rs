@@ -1,45 +1,189 @@
 use crate::flatbush::{Flatbush, FLATBUSH_DEFAULT_DEGREE};
 use crate::planar::algorithms::loop_relation::{find_loop_loop_relation, LoopLoopRelation};
-use crate::planar::types::Polygon;
+use crate::planar::primitives::{Envelope, HasEnvelope, Position};
+use crate::planar::properties::area::get_signed_loop_area;
+use crate::planar::types::{LineString, Polygon};
+use crate::planar::validation::report::ValidationReporter;
+use crate::planar::validation::{RingOrientation, ValidationError, ValidationErrorKind};
 use crate::Coordinate;
+use std::borrow::Cow;
 
 impl<C: Coordinate> Polygon<C> {
-    pub fn validate(&self) -> Result<(), &'static str> {
+    pub(crate) fn _validate(
+        &self,
+        reporter: &mut ValidationReporter<C>,
+    ) -> Result<(), ValidationError<C>> {
         if self.exterior.is_empty() && self.interiors.is_empty() {
             // Empty polygons are a valid empty geometry.
             return Ok(());
         }
-        if !self.exterior.is_closed() {
-            return Err("Exterior is not a loop.");
-        };
-        self.exterior.validate()?;
-        for interior in &self.interiors {
-            if !interior.is_closed() {
-                return Err("Interior linestring is not a loop.");
+        if reporter.options.allow_repeated_points && self._has_repeated_points() {
+            // The rings are checked against each other without them.
+            return self._without_repeated_points()._validate(reporter);
+        }
+
+        // Each ring must be a valid loop, before it's checked against the others.
+        let rings = std::iter::once(&self.exterior).chain(self.interiors.iter());
+        let mut is_valid_ring = Vec::with_capacity(self.interiors.len() + 1);
+        for (index, ring) in rings.enumerate() {
+            reporter.ring = Some(index);
+            let num_errors = reporter.num_errors();
+            if !ring.is_closed() {
+                reporter.report(_ring_not_closed_error(ring))?;
             };
-            interior.validate()?;
-            if find_loop_loop_relation(&self.exterior, &interior) != LoopLoopRelation::Contains {
-                return Err("Interior loop not contained in exterior loop.");
+            ring._validate(reporter)?;
+            let is_valid = reporter.num_errors() == num_errors;
+            if is_valid {
+                _report_wrong_orientation(ring, index == 0, reporter)?;
+            }
+            is_valid_ring.push(is_valid);
+        }
+        reporter.ring = None;
+
+        // The holes, with the index of the ring each is from.  A self-touching
+        // exterior encloses holes of its own, which are checked as it's split.
+        let mut shell = Cow::Borrowed(&self.exterior);
+        let mut holes: Vec<(usize, Cow<LineString<C>>)> = Vec::new();
+        if reporter.options.allow_self_touching_rings {
+            if is_valid_ring[0] {
+                let mut loops = self.exterior._split_loops();
+                let outer = (0..loops.len())
+                    .max_by(|&i, &j| {
+                        let area_i = get_signed_loop_area(&loops[i]).abs();
+                        let area_j = get_signed_loop_area(&loops[j]).abs();
+                        area_i.partial_cmp(&area_j).unwrap()
+                    })
+                    .unwrap();
+                shell = Cow::Owned(loops.swap_remove(outer));
+                for inner in loops {
+                    if find_loop_loop_relation(&shell, &inner) == LoopLoopRelation::Contains {
+                        holes.push((0, Cow::Owned(inner)));
+                    } else {
+                        // The exterior crosses itself where the loops meet.
+                        reporter.report(
+                            ValidationError::new(
+                                ValidationErrorKind::SelfIntersection,
+                                inner.positions.first().copied(),
+                            )
+                            .with_ring(0),
+                        )?;
+                    }
+                }
+            }
+            for (index, interior) in self.interiors.iter().enumerate() {
+                if is_valid_ring[index + 1] {
+                    let loops = interior._split_loops().into_iter().map(Cow::Owned);
+                    holes.extend(loops.map(|inner| (index + 1, inner)));
+                }
+            }
+        } else {
+            let interiors = self.interiors.iter().enumerate();
+            holes.extend(
+                interiors
+                    .filter(|&(index, _)| is_valid_ring[index + 1])
+                    .map(|(index, interior)| (index + 1, Cow::Borrowed(interior))),
+            );
+        }
+
+        for (ring, hole) in &holes {
+            if !is_valid_ring[0] || *ring == 0 {
+                continue;
+            }
+            if find_loop_loop_relation(&shell, hole) != LoopLoopRelation::Contains {
+                let position = _find_defect_position(&shell, hole);
+                reporter.report(
+                    ValidationError::new(ValidationErrorKind::HoleOutsideShell, position)
+                        .with_ring(*ring),
+                )?;
             }
         }
 
-        let rtree_of_interiors = Flatbush::new(&self.interiors, FLATBUSH_DEFAULT_DEGREE);
-        for (ls1_id, ls2_id) in rtree_of_interiors.find_self_intersection_candidates() {
-            let linestring_1 = &self.interiors[ls1_id];
-            let linestring_2 = &self.interiors[ls2_id];
+        let envelopes: Vec<Envelope<C>> = holes.iter().map(|(_, hole)| hole.envelope()).collect();
+        let rtree_of_holes = Flatbush::new(&envelopes, FLATBUSH_DEFAULT_DEGREE);
+        for (hole1_id, hole2_id) in rtree_of_holes.find_self_intersection_candidates() {
+            let (ring_1, linestring_1) = &holes[hole1_id];
+            let (ring_2, linestring_2) = &holes[hole2_id];
             if find_loop_loop_relation(linestring_1, linestring_2) != LoopLoopRelation::Separate {
-                return Err("Two Interior rings intersect.");
+                let position = _find_defect_position(linestring_1, linestring_2);
+                reporter.report(
+                    ValidationError::new(ValidationErrorKind::HolesIntersect, position)
+                        .with_ring(*ring_1)
+                        .with_other(*ring_2),
+                )?;
             }
         }
 
         Ok(())
     }
+
+    pub(crate) fn _has_repeated_points(&self) -> bool {
+        self.exterior._has_repeated_points()
+            || self
+                .interiors
+                .iter()
+                .any(|ring| ring._has_repeated_points())
+    }
+
+    pub(crate) fn _without_repeated_points(&self) -> Polygon<C> {
+        Polygon::new(
+            self.exterior._without_repeated_points(),
+            self.interiors
+                .iter()
+                .map(|ring| ring._without_repeated_points())
+                .collect(),
+        )
+    }
+}
+
+impl_validation!(Polygon);
+
+/// The error for a ring that isn't closed, located at its last position.
+fn _ring_not_closed_error<C: Coordinate>(ring: &LineString<C>) -> ValidationError<C> {
+    ValidationError::new(
+        ValidationErrorKind::RingNotClosed,
+        ring.positions.last().copied(),
+    )
+}
+
+/// Report a valid ring that winds the wrong way for the validity options.
+fn _report_wrong_orientation<C: Coordinate>(
+    ring: &LineString<C>,
+    is_exterior: bool,
+    reporter: &mut ValidationReporter<C>,
+) -> Result<(), ValidationError<C>> {
+    let exterior_ccw = match reporter.options.ring_orientation {
+        RingOrientation::Any => return Ok(()),
+        RingOrientation::ExteriorClockwise => false,
+        RingOrientation::ExteriorCounterClockwise => true,
+    };
+    if !ring.is_empty() && ring.is_ccw() != (exterior_ccw == is_exterior) {
+        reporter.report(ValidationError::new(
+            ValidationErrorKind::WrongOrientation,
+            ring.positions.first().copied(),
+        ))?;
+    }
+    Ok(())
+}
+
+/**
+ * Locate a defect between two loops: where they intersect, or failing that
+ * (when one is wrongly inside or outside the other), the second loop's start.
+ */
+pub(crate) fn _find_defect_position<C: Coordinate>(
+    loop_1: &LineString<C>,
+    loop_2: &LineString<C>,
+) -> Option<Position<C>> {
+    loop_1
+        ._find_intersection_position(loop_2)
+        .or_else(|| loop_2.positions.first().copied())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::planar::primitives::PrecisionModel;
     use crate::planar::types::LineString;
+    use crate::planar::validation::{ValidationErrorKind, ValidityOptions};
 
     #[test]
     fn test_valid_microsoft_examples() {
@@ -228,4 +372,168 @@ mod tests {
         .validate()
         .is_err());
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn check_error_location() {
+        let square = vec![(-5., -5.), (-5., 5.), (5., 5.), (5., -5.), (-5., -5.)];
+        let error = Polygon::new(
+            LineString::from(square.clone()),
+            vec![
+                LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 0.)]),
+                LineString::from(vec![(3., 0.), (6., 0.), (6., 3.), (3., 3.), (3., 0.)]),
+            ],
+        )
+        .validate()
+        .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::HoleOutsideShell);
+        assert_eq!(error.ring, Some(2));
+        // Either of the crossings of the exterior.
+        assert_eq!(error.position.unwrap().x, 5.);
+
+        let error = Polygon::new(
+            LineString::from(square),
+            vec![LineString::from(vec![(0., 0.), (1., 0.), (1., 1.)])],
+        )
+        .validate()
+        .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::RingNotClosed);
+        assert_eq!(error.ring, Some(1));
+        assert_eq!(error.position, Some(Position::new(1., 1.)));
+    }
+
+    #[test]
+    fn check_self_touching_exterior() {
+        // A clockwise exterior that touches itself at (2, 0), around a hole.
+        let polygon = Polygon::from(vec![
+            (0., 0.),
+            (0., 4.),
+            (4., 4.),
+            (4., 0.),
+            (2., 0.),
+            (3., 2.),
+            (1., 2.),
+            (2., 0.),
+            (0., 0.),
+        ]);
+        let error = polygon.validate().unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::SelfIntersection);
+        assert!(polygon
+            .validate_with_options(&ValidityOptions::esri())
+            .is_ok());
+
+        let options = ValidityOptions {
+            ring_orientation: RingOrientation::ExteriorCounterClockwise,
+            ..ValidityOptions::esri()
+        };
+        let error = polygon.validate_with_options(&options).unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::WrongOrientation);
+        assert_eq!(error.ring, Some(0));
+
+        // The loops of a figure eight don't enclose each other.
+        let figure_eight = Polygon::from(vec![
+            (0., 0.),
+            (2., 2.),
+            (4., 0.),
+            (4., 4.),
+            (2., 2.),
+            (0., 4.),
+            (0., 0.),
+        ]);
+        let options = ValidityOptions {
+            allow_self_touching_rings: true,
+            ..ValidityOptions::ogc()
+        };
+        let error = figure_eight.validate_with_options(&options).unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::SelfIntersection);
+        assert_eq!(error.position, Some(Position::new(2., 2.)));
+    }
+
+    #[test]
+    fn check_validity_options() {
+        let polygon = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (4., 0.),
+                (4., 4.),
+                (0., 4.),
+                (0., 0.),
+            ]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (1., 2.),
+                (2., 2.),
+                (2., 1.),
+                (1., 1.),
+            ])],
+        );
+        let error = polygon.validate().unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::RepeatedPoint);
+        let options = ValidityOptions {
+            allow_repeated_points: true,
+            ..ValidityOptions::ogc()
+        };
+        assert!(polygon.validate_with_options(&options).is_ok());
+
+        // The exterior is counterclockwise and the interior clockwise.
+        let options = ValidityOptions {
+            ring_orientation: RingOrientation::ExteriorClockwise,
+            ..options
+        };
+        let report = polygon.validation_report_with_options(&options);
+        assert_eq!(report.len(), 2);
+        assert!(report
+            .iter()
+            .all(|e| e.kind == ValidationErrorKind::WrongOrientation));
+        let options = ValidityOptions {
+            ring_orientation: RingOrientation::ExteriorCounterClockwise,
+            ..options
+        };
+        assert!(polygon.validate_with_options(&options).is_ok());
+
+        let options = ValidityOptions {
+            allow_repeated_points: true,
+            ..ValidityOptions::rfc7946()
+        };
+        let error = polygon
+            .orient(false)
+            .validate_with_options(&options)
+            .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::WrongOrientation);
+        assert_eq!(error.ring, Some(0));
+        assert!(polygon.orient(true).validate_with_options(&options).is_ok());
+
+        let ls = LineString::from(vec![(1., 1.), (1., 1.)]);
+        let error = ls.validate_with_options(&options).unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::TooFewPoints);
+    }
+
+    #[test]
+    fn check_validate_with_precision() {
+        // The fourth position is just above the bottom edge, which it
+        // touches once rounded to 7 decimal places.
+        let polygon = Polygon::from(vec![
+            (0., 0.),
+            (4., 0.),
+            (4., 4.),
+            (2., 0.00000004),
+            (0., 4.),
+            (0., 0.),
+        ]);
+        let options = ValidityOptions::default();
+        assert!(polygon.validate().is_ok());
+        let model = PrecisionModel::decimal_places(8).unwrap();
+        assert!(polygon.validate_with_precision(&options, &model).is_ok());
+        let model = PrecisionModel::decimal_places(7).unwrap();
+        let error = polygon
+            .validate_with_precision(&options, &model)
+            .unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::SelfIntersection);
+        assert_eq!(error.position, Some(Position::new(2., 0.)));
+        assert_eq!(
+            polygon.validation_report_with_precision(&options, &model),
+            vec![error]
+        );
+    }
 }
@@ -0,0 +1,136 @@
+use crate::planar::validation::{ValidationError, ValidityOptions};
+use crate::Coordinate;
+
+/**
+ * Where validation sends the defects it finds.
+ *
+ * When validating, the first defect is returned as an error, which stops the
+ * validation.  When building a report, every defect is collected, and the
+ * validation keeps going.  The validation code reports each defect with
+ * `reporter.report(error)?`, so it is the same for both.
+ *
+ * `part` and `ring` are the indices of what is currently being validated,
+ * and are filled into reported errors that don't have their own.  `options`
+ * is the validity model being checked.
+ */
+pub(crate) struct ValidationReporter<C: Coordinate> {
+    pub(crate) errors: Vec<ValidationError<C>>,
+    pub(crate) part: Option<usize>,
+    pub(crate) ring: Option<usize>,
+    pub(crate) options: ValidityOptions,
+    exhaustive: bool,
+}
+
+impl<C: Coordinate> ValidationReporter<C> {
+    pub(crate) fn first(options: ValidityOptions) -> Self {
+        ValidationReporter {
+            errors: Vec::new(),
+            part: None,
+            ring: None,
+            options,
+            exhaustive: false,
+        }
+    }
+
+    pub(crate) fn exhaustive(options: ValidityOptions) -> Self {
+        ValidationReporter {
+            exhaustive: true,
+            ..ValidationReporter::first(options)
+        }
+    }
+
+    pub(crate) fn report(
+        &mut self,
+        mut error: ValidationError<C>,
+    ) -> Result<(), ValidationError<C>> {
+        if error.part.is_none() {
+            error.part = self.part;
+        }
+        if error.ring.is_none() {
+            error.ring = self.ring;
+        }
+        if self.exhaustive {
+            self.errors.push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// The number of defects found so far, to tell whether a step found any.
+    pub(crate) fn num_errors(&self) -> usize {
+        self.errors.len()
+    }
+}
+
+/**
+ * Implement `validate` and `validation_report` for a type, using its
+ * `_validate(&self, reporter)`.
+ */
+macro_rules! impl_validation {
+    ($type:ident) => {
+        impl<C: Coordinate> $type<C> {
+            /// Validate the geometry, returning the first defect found.
+            pub fn validate(&self) -> Result<(), ValidationError<C>> {
+                self.validate_with_options(&$crate::planar::validation::ValidityOptions::default())
+            }
+
+            /// Validate the geometry against a validity model, returning the
+            /// first defect found.
+            pub fn validate_with_options(
+                &self,
+                options: &$crate::planar::validation::ValidityOptions,
+            ) -> Result<(), ValidationError<C>> {
+                self._validate(&mut ValidationReporter::first(*options))
+            }
+
+            /**
+             * Find every defect in the geometry, in every part, rather than
+             * stopping at the first.  The result is empty if it is valid.
+             */
+            pub fn validation_report(&self) -> Vec<ValidationError<C>> {
+                self.validation_report_with_options(
+                    &$crate::planar::validation::ValidityOptions::default(),
+                )
+            }
+
+            /// Find every defect in the geometry against a validity model.
+            pub fn validation_report_with_options(
+                &self,
+                options: &$crate::planar::validation::ValidityOptions,
+            ) -> Vec<ValidationError<C>> {
+                let mut reporter = ValidationReporter::exhaustive(*options);
+                // An exhaustive reporter never stops the validation.
+                let _ = self._validate(&mut reporter);
+                reporter.errors
+            }
+
+            /**
+             * Validate the geometry as it would be once rounded to the
+             * precision model.  Positions in the error are on the grid.
+             */
+            pub fn validate_with_precision(
+                &self,
+                options: &$crate::planar::validation::ValidityOptions,
+                precision_model: &$crate::planar::primitives::PrecisionModel<C>,
+            ) -> Result<(), ValidationError<C>> {
+                self.snap_to_grid(precision_model)
+                    .validate_with_options(options)
+                    .map_err(|error| error._make_precise(precision_model))
+            }
+
+            /// Find every defect in the geometry once rounded to the precision model.
+            pub fn validation_report_with_precision(
+                &self,
+                options: &$crate::planar::validation::ValidityOptions,
+                precision_model: &$crate::planar::primitives::PrecisionModel<C>,
+            ) -> Vec<ValidationError<C>> {
+                self.snap_to_grid(precision_model)
+                    .validation_report_with_options(options)
+                    .into_iter()
+                    .map(|error| error._make_precise(precision_model))
+                    .collect()
+            }
+        }
+    };
+}